@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// What a puzzle part produces. Most answers are numbers, but some puzzles want
+/// a word, or a picture drawn in a grid of characters.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i64),
+    /// For when the product or sum of something outgrows 64 bits.
+    BigInt(i128),
+    /// Cow so that examples can spell out text answers in a const.
+    Text(Cow<'static, str>),
+    /// One string per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// The numeric value, if this is a number at all.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n as i128),
+            Answer::BigInt(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::BigInt(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+            Answer::Grid(rows) => rows.join("\n").fmt(f),
+        }
+    }
+}
+
+/// Answers are equal if they'd be submitted the same way, so `Int(5)`,
+/// `BigInt(5)`, and `Text("5")` are all the same answer.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// So tests can just say `assert_eq!(answer, 357)`.
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_i128() == Some(*other as i128)
+    }
+}
+
+/// Reads back something we printed (or something a person typed): numbers
+/// become numbers, multi-line text becomes a grid, and anything else is text.
+/// Only numbers written the way we'd print them count, so a text answer like
+/// `0123` or `+5` stays text and keeps its zero or its plus.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(n) = s.parse::<i128>().ok().filter(|n| n.to_string() == s) {
+            Ok(Answer::from(n))
+        } else if s.contains('\n') {
+            Ok(Answer::Grid(s.lines().map(String::from).collect()))
+        } else {
+            Ok(Answer::Text(s.to_string().into()))
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Int(value)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::from(value as i128)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::from(value as i128)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string().into())
+    }
+}
+
+#[test]
+fn conversions_test() {
+    assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+    assert!(matches!(Answer::from(5_u64), Answer::Int(5)));
+    assert!(matches!(Answer::from(5_usize), Answer::Int(5)));
+    assert_eq!(Answer::from(-3_i32), -3);
+}
+
+#[test]
+fn from_str_test() {
+    let parse = |s: &str| s.parse::<Answer>().unwrap();
+    assert!(matches!(parse("1036"), Answer::Int(1036)));
+    assert!(matches!(parse("-7"), Answer::Int(-7)));
+    assert!(matches!(parse("99999999999999999999"), Answer::BigInt(_)));
+    assert!(matches!(parse("abc"), Answer::Text(_)));
+    assert!(matches!(parse("#.\n.#"), Answer::Grid(rows) if rows.len() == 2));
+    assert!(matches!(parse("0123"), Answer::Text(_)));
+    assert!(matches!(parse("+5"), Answer::Text(_)));
+    assert!(matches!(parse("-0"), Answer::Text(_)));
+    assert!(matches!(parse("0"), Answer::Int(0)));
+}
+
+#[test]
+fn round_trip_test() {
+    let answers = [
+        Answer::Int(-7),
+        Answer::BigInt(i128::MAX),
+        Answer::Text("0123".into()),
+        Answer::Text("+5".into()),
+        Answer::Text("HZLEHJRK".into()),
+        Answer::Grid(vec!["#.".to_string(), ".#".to_string()]),
+    ];
+    for answer in answers {
+        let Ok(back) = answer.to_string().parse::<Answer>();
+        assert_eq!(back, answer);
+        assert_eq!(back.as_i128(), answer.as_i128(), "{answer} changed kind");
+    }
+}
+
+#[test]
+fn equality_test() {
+    assert_eq!(Answer::Int(5), Answer::BigInt(5));
+    assert_eq!(Answer::Int(5), Answer::from("5"));
+    assert_ne!(Answer::Int(5), Answer::from("05"));
+    assert_eq!(
+        Answer::Grid(vec!["#.".to_string(), ".#".to_string()]),
+        Answer::from("#.\n.#")
+    );
+    assert_ne!(Answer::from("five"), 5);
+}
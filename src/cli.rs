@@ -0,0 +1,540 @@
+//! Command-line handling for the `aoc25` binary.
+
+use crate::answer::Answer;
+use crate::failure::Failure;
+use crate::fixture;
+use crate::guesses;
+use crate::ledger::{self, Ledger, Status};
+use crate::meta;
+use crate::pool::{self, Job, JobResult};
+use crate::report::Format;
+use crate::runner::{
+    BatchOptions, bench, input_string, ledger_key, parse_duration, parse_puzzle, puzzle_fn,
+    puzzle_params, read_path_or_stdin, report_one, run_days, run_examples, run_part, select_days,
+    workspace_root,
+};
+use crate::site;
+use crate::solution::{self, Id};
+use crate::util::log::{self, Level};
+use crate::watch;
+use anyhow::Context;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const USAGE: &str = "\
+usage: aoc25 <PUZZLE> [--time] [--input <PATH>] [--timeout T]
+                                                 run one part, like `1-1`
+       aoc25 <PUZZLE> --example                  run one part against the examples from the puzzle text
+       aoc25 params <DAY>                        list a day's params and their defaults
+       aoc25 <DAYS> [--time] [--jobs N] [--timeout T]
+                                                 run both parts of `all` days, or a range like `3..7`
+       aoc25 bench <PUZZLE> [RUNS] [--input <PATH>]
+                                                 time one part over several runs
+       aoc25 record <PUZZLE> [ANSWER]            save a known-correct answer to the ledger
+       aoc25 submit <PUZZLE> [ANSWER]            send an answer (or whatever the part gives now) to the site
+       aoc25 watch <PUZZLE>                      re-run one part and its examples whenever its source or input changes
+       aoc25 make <DAY_NUM> [--variant V] [--template NAME] [--title TITLE] [--dry-run]
+                                                 scaffold a new day (or a variant of one, like `11b`),
+                                                 or just show what that would do
+       aoc25 unmake <DAY> [--dry-run]            delete a day (or a variant, like `11b`), its input, and its fixtures
+       aoc25 example <DAY> --part P --answer A [--input <PATH>]
+                                                 save an example (from stdin, byte for byte) as a test fixture
+       aoc25 fetch <DAY>                         download a day's input into inputs/, unless it's already there
+       aoc25 sync [--dry-run]                    rebuild dispatch.rs from the day modules in src/dispatch/
+
+`fetch` and `submit` log in with the session cookie in AOC25_SESSION, and talk to AOC25_URL
+if that's set instead of the real site. They wait a few seconds between requests.
+`submit` keeps every guess in guesses.log, won't send an answer that's already been
+judged wrong, and puts right ones in the ledger.
+--input reads the puzzle input from PATH instead of inputs/dayN.txt; use `-` for stdin.
+The ledger only knows answers for our own inputs, so those runs don't get checked.
+Puzzles and days can start with a year, like `2024/5-2` or `2024/3..7`, to reach
+back to past events (their inputs go in inputs/2024/); without one, they're 2025's.
+A variant runs as its own puzzle, like `11b-1`, and gets checked against the original's
+answers. Ranges of days only run the originals.
+`make` templates: plain (the default), grid, lines, and sections.
+--format json prints results as JSON instead of text, when running a single puzzle
+or a range of days.
+--param NAME=VALUE overrides one of a puzzle's params (repeatable); it works anywhere a
+single puzzle runs, and also skips the ledger check.
+
+--timeout T (like `30s`) gives up on any part that takes longer, reporting it as TIMEOUT.
+--jobs N runs that many parts of a range of days at once.
+
+-v shows the days' debug output on stderr, and -vv shows all of it. -q prints just the answer.
+
+exit codes: 1 crash or implementation error, 2 usage, 3 missing input, 4 wrong answer,
+5 timeout, 6 the site didn't judge a submitted answer (rate-limited, wrong-level, or unclear)";
+
+/// Pulls a boolean flag out of the arg list wherever it is, so the positional
+/// args can stay positional.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != flag);
+    args.len() != before
+}
+
+/// Pulls every `--param NAME=VALUE` out of the arg list.
+fn take_params(args: &mut Vec<String>) -> Result<Vec<(String, String)>, Failure> {
+    let mut params = Vec::new();
+    while let Some(param) = take_option(args, "--param")? {
+        let (name, value) = param.split_once('=').ok_or_else(|| {
+            Failure::Usage(format!(
+                "`--param` wants NAME=VALUE, like `connect=10`.\n\n{USAGE}"
+            ))
+        })?;
+        params.push((name.to_string(), value.to_string()));
+    }
+    // take_option works front to back, so later ones win like you'd expect.
+    Ok(params)
+}
+
+/// Pulls `--name VALUE` out of the arg list wherever it is.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Failure> {
+    let Some(i) = args.iter().position(|a| a == name) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err(Failure::Usage(format!(
+            "`{name}` requires a value.\n\n{USAGE}"
+        )));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+/// A day number, maybe with a year in front, like `2024/5`. No variants.
+fn year_and_day(day: &str) -> Option<(u32, u32)> {
+    match solution::parse_day(day)? {
+        (year, day, "") => Some((year, day)),
+        _ => None,
+    }
+}
+
+/// Only for the commands that check or record answers, so a broken ledger
+/// doesn't get in the way of the ones that don't.
+fn load_ledger() -> Result<Ledger, Failure> {
+    Ok(Ledger::load(workspace_root().join(ledger::LEDGER_PATH))
+        .context("couldn't read the answer ledger")?)
+}
+
+/// The answer for `record` or `submit`: either the one given, or whatever
+/// the current implementation says, trusting it.
+fn compute_answer(puzzle: &str, given: Option<String>) -> Result<Answer, Failure> {
+    if let Some(given) = given {
+        let Ok(answer) = given.parse::<Answer>();
+        return Ok(answer);
+    }
+    let f = puzzle_fn(puzzle)?;
+    let params = puzzle_params(puzzle, &[])?;
+    Ok(run_part(puzzle, f, &input_string(puzzle, None)?, &params)?)
+}
+
+/// `parse_puzzle`, plus the usage text when that's not a puzzle id at all.
+fn puzzle_id(p: &str) -> Result<Id<'_>, Failure> {
+    parse_puzzle(p).map_err(|e| Failure::Usage(format!("{e}\n\n{USAGE}")))
+}
+
+/// Everything `main` does, minus the exit code.
+pub fn run(mut args: Vec<String>) -> Result<(), Failure> {
+    let usage = |msg: &str| Failure::Usage(format!("{msg}\n\n{USAGE}"));
+
+    let quiet = take_flag(&mut args, "-q");
+    let very_verbose = take_flag(&mut args, "-vv");
+    let verbose = take_flag(&mut args, "-v");
+    log::set_level(match (quiet, verbose, very_verbose) {
+        (true, false, false) => Level::Quiet,
+        (false, false, false) => Level::Info,
+        (false, true, false) => Level::Debug,
+        (false, _, true) => Level::Trace,
+        _ => return Err(usage("`-q` and `-v` are opposites; pick one.")),
+    });
+    let time = take_flag(&mut args, "--time");
+    let example = take_flag(&mut args, "--example");
+    let dry_run = take_flag(&mut args, "--dry-run");
+    let variant = take_option(&mut args, "--variant")?;
+    let template = take_option(&mut args, "--template")?;
+    let title = take_option(&mut args, "--title")?;
+    let part = take_option(&mut args, "--part")?;
+    let answer = take_option(&mut args, "--answer")?;
+    let input_override = take_option(&mut args, "--input")?;
+    let input_override = input_override.as_deref();
+    let overrides = take_params(&mut args)?;
+    let format = match take_option(&mut args, "--format")? {
+        Some(f) => f.parse::<Format>().map_err(|e| usage(&format!("{e}.")))?,
+        None => Format::Table,
+    };
+    let jobs = match take_option(&mut args, "--jobs")? {
+        Some(n) => n
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| usage("`--jobs` should be a positive number."))?,
+        None => 1,
+    };
+    let timeout = match take_option(&mut args, "--timeout")? {
+        Some(t) => Some(
+            parse_duration(&t)
+                .ok_or_else(|| usage("`--timeout` should be like `30s`, `500ms`, or `2m`."))?,
+        ),
+        None => None,
+    };
+    let mut args = args.into_iter();
+    let first_arg = args.next().ok_or_else(|| usage("Requires an argument."))?;
+
+    if first_arg == "make" {
+        let (year, day) = args
+            .next()
+            .as_deref()
+            .and_then(year_and_day)
+            .ok_or_else(|| usage("`make` requires a day number as a second argument."))?;
+        let defaults = meta::MakeOptions::default();
+        let options = meta::MakeOptions {
+            variant: variant.as_deref().unwrap_or(defaults.variant),
+            template: template.as_deref().unwrap_or(defaults.template),
+            title: title.as_deref().unwrap_or(defaults.title),
+        };
+        let name = format!("{}{day}{}", solution::year_prefix(year), options.variant);
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let plan = meta::plan_new_day(crate_dir, &workspace_root(), year, day, &options)
+            .with_context(|| format!("couldn't create day {name}; nothing was changed"))?;
+        if dry_run {
+            print!("{}", plan.describe());
+            return Ok(());
+        }
+        meta::apply(&plan)
+            .with_context(|| format!("couldn't create day {name}; nothing was changed"))?;
+        println!("created day {name}");
+        return Ok(());
+    }
+    if variant.is_some() || template.is_some() || title.is_some() {
+        return Err(usage(
+            "`--variant`, `--template`, and `--title` only go with `make`.",
+        ));
+    }
+
+    if first_arg == "unmake" {
+        let name = args.next().ok_or_else(|| {
+            usage("`unmake` requires a day (like `11` or `11b`) as a second argument.")
+        })?;
+        let (year, day, variant) = solution::parse_day(&name).ok_or_else(|| {
+            usage(&format!(
+                "`{name}` isn't a day; expected something like `11` or `11b`."
+            ))
+        })?;
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let plan = meta::plan_unmake(crate_dir, &workspace_root(), year, day, variant)
+            .with_context(|| format!("couldn't delete day {name}; nothing was changed"))?;
+        if dry_run {
+            print!("{}", plan.describe());
+            return Ok(());
+        }
+        meta::apply(&plan)
+            .with_context(|| format!("couldn't delete day {name}; nothing was changed"))?;
+        println!("deleted day {name}");
+        return Ok(());
+    }
+
+    if first_arg == "sync" {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for mismatch in meta::mismatches(crate_dir, &workspace_root())? {
+            println!("heads up: {mismatch}");
+        }
+        let plan = meta::plan_sync(crate_dir)?;
+        if dry_run {
+            print!("{}", plan.describe());
+        } else if plan.changes_anything() {
+            meta::apply(&plan).context("couldn't rewrite dispatch.rs; nothing was changed")?;
+            println!("rewrote dispatch.rs; rebuild to pick it up");
+        } else {
+            println!("dispatch.rs is already in sync");
+        }
+        return Ok(());
+    }
+    if dry_run {
+        return Err(usage(
+            "`--dry-run` only goes with `make`, `unmake`, and `sync`.",
+        ));
+    }
+
+    if first_arg == "example" {
+        let (year, day) = args
+            .next()
+            .as_deref()
+            .and_then(year_and_day)
+            .filter(|(_, d)| (1..=25).contains(d))
+            .ok_or_else(|| usage("`example` requires a day number as a second argument."))?;
+        let part = part
+            .and_then(|p| p.parse::<u8>().ok())
+            .filter(|p| [1, 2].contains(p))
+            .ok_or_else(|| usage("`example` requires `--part 1` or `--part 2`."))?;
+        let Ok(answer) = answer
+            .ok_or_else(|| usage("`example` requires the expected `--answer`."))?
+            .parse::<Answer>();
+        // Can't check params against a day we haven't been rebuilt with yet.
+        if let Some(puzzle) = solution::find_in(year, day, "") {
+            puzzle
+                .params(overrides.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+                .map_err(|e| Failure::Usage(format!("{e}.")))?;
+        }
+        let input = read_path_or_stdin(input_override.unwrap_or("-"))?;
+        let path = fixture::save(
+            &fixture::fixtures_dir(),
+            year,
+            day,
+            part,
+            &input,
+            &answer,
+            &overrides,
+        )
+        .context("couldn't save the example")?;
+        println!(
+            "saved {} ({} bytes); `{}{day}-{part} --example` and the tests will check it",
+            path.display(),
+            input.len(),
+            solution::year_prefix(year)
+        );
+        return Ok(());
+    }
+    if part.is_some() || answer.is_some() {
+        return Err(usage("`--part` and `--answer` only go with `example`."));
+    }
+
+    if first_arg == "fetch" {
+        let (year, day) = args
+            .next()
+            .as_deref()
+            .and_then(year_and_day)
+            .filter(|(_, d)| (1..=25).contains(d))
+            .ok_or_else(|| usage("`fetch` requires a day number as a second argument."))?;
+        let root = workspace_root();
+        let name = format!("{}{day}", solution::year_prefix(year));
+        let fetched = site::Site::from_env(&root)
+            .fetch(&root, year, day)
+            .with_context(|| format!("couldn't fetch the input for day {name}"))?;
+        match fetched {
+            site::Fetched::Downloaded(path) => println!("saved {}", path.display()),
+            site::Fetched::Cached(path) => println!("already have {}", path.display()),
+        }
+        return Ok(());
+    }
+
+    if first_arg == "bench" {
+        if time || example || format == Format::Json || jobs != 1 || timeout.is_some() {
+            return Err(usage(
+                "`bench` always times, and only takes a run count, `--input`, and `--param`.",
+            ));
+        }
+        let puzzle = args
+            .next()
+            .ok_or_else(|| usage("`bench` requires a puzzle (like `1-1`) as a second argument."))?;
+        let runs = match args.next() {
+            Some(n) => n
+                .parse::<usize>()
+                .map_err(|_| usage("`bench` run count should be a number."))?,
+            None => 10,
+        };
+        puzzle_id(&puzzle)?;
+        return bench(&puzzle, runs, input_override, &overrides);
+    }
+
+    if first_arg == "watch" {
+        if input_override.is_some() || !overrides.is_empty() {
+            return Err(usage(
+                "`watch` runs a part on our own input and its examples, so it doesn't take `--input` or `--param`.",
+            ));
+        }
+        if time || example || format == Format::Json || jobs != 1 || timeout.is_some() {
+            return Err(usage(
+                "`watch` always checks the examples too, and doesn't take `--time`, \
+                 `--example`, `--format`, `--jobs`, or `--timeout`.",
+            ));
+        }
+        let puzzle = args
+            .next()
+            .ok_or_else(|| usage("`watch` requires a puzzle (like `1-1`) as a second argument."))?;
+        puzzle_id(&puzzle)?;
+        puzzle_fn(&puzzle)?;
+        let found = solution::find_id(&puzzle).expect("puzzle_fn already checked");
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        return watch::watch(&puzzle, found, crate_dir, &workspace_root());
+    }
+
+    if first_arg == "params" {
+        let (year, day) = args
+            .next()
+            .as_deref()
+            .and_then(year_and_day)
+            .ok_or_else(|| usage("`params` requires a day number as a second argument."))?;
+        let name = format!("{}{day}", solution::year_prefix(year));
+        let puzzle = solution::find_in(year, day, "")
+            .ok_or_else(|| Failure::Usage(format!("day {name} isn't registered yet.")))?;
+        if puzzle.params.is_empty() {
+            println!("day {name} doesn't take any params.");
+        }
+        for param in puzzle.params {
+            match param.sensible() {
+                Some(sensible) => println!(
+                    "{} (default {}, {sensible}): {}",
+                    param.name, param.default, param.help
+                ),
+                None => println!("{} (default {}): {}", param.name, param.default, param.help),
+            }
+        }
+        return Ok(());
+    }
+
+    if first_arg == "record" {
+        if input_override.is_some() || !overrides.is_empty() {
+            return Err(usage(
+                "`record` only saves answers for our own inputs, so it doesn't take `--input` or `--param`.",
+            ));
+        }
+        let puzzle = args.next().ok_or_else(|| {
+            usage("`record` requires a puzzle (like `1-1`) as a second argument.")
+        })?;
+        puzzle_id(&puzzle)?;
+        let answer = compute_answer(&puzzle, args.next())?;
+        load_ledger()?
+            .record(&ledger_key(&puzzle)?, &answer)
+            .context("couldn't write the answer ledger")?;
+        println!("recorded {puzzle}: {answer}");
+        return Ok(());
+    }
+
+    if first_arg == "submit" {
+        if input_override.is_some() || !overrides.is_empty() {
+            return Err(usage(
+                "`submit` only sends answers for our own inputs, so it doesn't take `--input` or `--param`.",
+            ));
+        }
+        let puzzle = args.next().ok_or_else(|| {
+            usage("`submit` requires a puzzle (like `1-1`) as a second argument.")
+        })?;
+        let id = puzzle_id(&puzzle)?;
+        let key = id.ledger_key();
+        let answer = compute_answer(&puzzle, args.next())?;
+        if let Answer::Grid(_) = answer {
+            return Err(usage(
+                "The site wants the letters in a picture, not the picture; read them off and pass them as ANSWER.",
+            ));
+        }
+
+        // Before sending anything, so a right answer has somewhere to go.
+        let mut ledger = load_ledger()?;
+        let root = workspace_root();
+        let mut history = guesses::History::load(root.join(guesses::GUESSES_PATH))
+            .context("couldn't read the guess history")?;
+        let warnings = history
+            .check(&key, &answer)
+            .map_err(|e| Failure::Usage(format!("not submitting: {e}.")))?;
+        for warning in warnings {
+            println!("heads up: {warning}");
+        }
+        let (verdict, message) = site::Site::from_env(&root)
+            .submit(id.year, id.day, id.part, &answer)
+            .with_context(|| format!("couldn't submit {answer} for {puzzle}"))?;
+        history
+            .record(&key, &answer, verdict)
+            .context("couldn't write the guess history")?;
+        println!("{puzzle}: {answer} is {verdict}\n{message}");
+        // The exit code says how it went too, for scripts.
+        return match verdict {
+            guesses::Verdict::Right => {
+                ledger
+                    .record(&key, &answer)
+                    .context("couldn't write the answer ledger")?;
+                println!("recorded {puzzle}: {answer}");
+                Ok(())
+            }
+            verdict if verdict.is_wrong() => Err(Failure::WrongAnswer),
+            verdict => Err(Failure::Unjudged(verdict.to_string())),
+        };
+    }
+
+    if let Some(days) = select_days(&first_arg) {
+        if input_override.is_some() || example || !overrides.is_empty() {
+            return Err(usage(
+                "`--input`, `--example`, and `--param` only work when running a single puzzle.",
+            ));
+        }
+        let options = BatchOptions {
+            time,
+            format,
+            jobs,
+            timeout,
+        };
+        return run_days(&days, &load_ledger()?, &options);
+    }
+    if jobs != 1 {
+        return Err(usage("`--jobs` only works when running a range of days."));
+    }
+
+    let puzzle = first_arg;
+    puzzle_id(&puzzle)?;
+    if example {
+        if input_override.is_some() || format == Format::Json || timeout.is_some() {
+            return Err(usage(
+                "`--example` doesn't go with `--input`, `--format json`, or `--timeout`.",
+            ));
+        }
+        return run_examples(&puzzle, &overrides, time);
+    }
+    let f = puzzle_fn(&puzzle)?;
+    let params = puzzle_params(&puzzle, &overrides)?;
+    let input = match input_string(&puzzle, input_override) {
+        // JSON readers get a record either way.
+        Err(missing @ Failure::NoInput { .. }) if format == Format::Json => {
+            return report_one(&puzzle, Err(missing), None);
+        }
+        input => input?,
+    };
+    let job = Job {
+        id: puzzle.clone(),
+        f,
+        input: Arc::new(input),
+        params,
+    };
+    let result = pool::run_job(&job, timeout);
+    // The ledger's answers are for our own inputs with the default params.
+    let ledger = match input_override.is_none() && overrides.is_empty() {
+        true => Some(load_ledger()?),
+        false => None,
+    };
+    if format == Format::Json {
+        return report_one(&puzzle, Ok(result), ledger.as_ref());
+    }
+    let (output, elapsed) = match result {
+        JobResult::Done(output, elapsed) => (output?, elapsed),
+        JobResult::TimedOut(after) => return Err(Failure::Timeout { id: puzzle, after }),
+    };
+
+    // With -q, it's just the answer; the exit code still says how it went.
+    let chatty = log::enabled(Level::Info);
+    if chatty {
+        println!("got output:");
+    }
+    println!("{output}");
+    if time {
+        println!("took {elapsed:.2?}");
+    }
+    let Some(ledger) = ledger else {
+        return Ok(());
+    };
+    let status = ledger.check(&ledger_key(&puzzle)?, &output);
+    match status {
+        Status::Fail { expected } => {
+            if chatty {
+                println!("FAIL: expected\n{expected}");
+            }
+            Err(Failure::WrongAnswer)
+        }
+        status => {
+            if chatty {
+                println!("{status}");
+            }
+            Ok(())
+        }
+    }
+}
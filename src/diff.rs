@@ -0,0 +1,140 @@
+//! Just enough of a unified diff to preview what `make` is about to do to a
+//! file. Plain LCS on lines, which is quadratic, but the files it sees are a
+//! few dozen lines long.
+
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// `diff -u` style output, or an empty string if there's no difference.
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old, &new);
+    if lines.iter().all(|l| matches!(l, Line::Same(_))) {
+        return String::new();
+    }
+
+    let mut out = format!("--- {old_name}\n+++ {new_name}\n");
+    for hunk in hunks(&lines) {
+        // Line numbers where the hunk starts, on each side.
+        let (mut old_line, mut new_line) = (1, 1);
+        for line in &lines[..hunk.start] {
+            match line {
+                Line::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+                Line::Removed(_) => old_line += 1,
+                Line::Added(_) => new_line += 1,
+            }
+        }
+        let body = &lines[hunk];
+        let old_len = body.iter().filter(|l| !matches!(l, Line::Added(_))).count();
+        let new_len = body
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_line, old_len),
+            range(new_line, new_len)
+        ));
+        for line in body {
+            match line {
+                Line::Same(l) => out.push_str(&format!(" {l}\n")),
+                Line::Removed(l) => out.push_str(&format!("-{l}\n")),
+                Line::Added(l) => out.push_str(&format!("+{l}\n")),
+            }
+        }
+    }
+    out
+}
+
+/// Like `3,4`. An empty range points at the line before it, per tradition.
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start - 1),
+        1 => start.to_string(),
+        _ => format!("{start},{len}"),
+    }
+}
+
+/// Longest common subsequence, then walk it to get the edit script.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // lcs[i][j] is the LCS length of old[i..] and new[j..].
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push(Line::Same(old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            // Removals go first when it's a toss-up, like diff does it.
+            out.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            out.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+/// Index ranges of changes plus their context, merging any that overlap.
+fn hunks(lines: &[Line]) -> Vec<std::ops::Range<usize>> {
+    let mut out: Vec<std::ops::Range<usize>> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if matches!(line, Line::Same(_)) {
+            continue;
+        }
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + 1 + CONTEXT).min(lines.len());
+        match out.last_mut() {
+            Some(last) if start <= last.end => last.end = last.end.max(end),
+            _ => out.push(start..end),
+        }
+    }
+    out
+}
+
+#[test]
+fn unified_test() {
+    let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+    let new = "a\nb\nc\nD\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\n";
+    assert_eq!(
+        unified(old, new, "old", "new"),
+        "--- old\n+++ new\n\
+         @@ -1,7 +1,7 @@\n a\n b\n c\n-d\n+D\n e\n f\n g\n\
+         @@ -11,3 +11,4 @@\n k\n l\n m\n+n\n"
+    );
+    // Changes close enough together share a hunk.
+    let close = unified("a\nb\nc\n", "A\nb\nC\n", "old", "new");
+    assert_eq!(close.matches("@@ -").count(), 1);
+}
+
+#[test]
+fn unified_edge_cases_test() {
+    assert_eq!(unified("same\n", "same\n", "a", "b"), "");
+    assert_eq!(
+        unified("", "new\n", "a", "b"),
+        "--- a\n+++ b\n@@ -0,0 +1 @@\n+new\n"
+    );
+    assert_eq!(
+        unified("old\n", "", "a", "b"),
+        "--- a\n+++ b\n@@ -1 +0,0 @@\n-old\n"
+    );
+}
@@ -1,45 +1,24 @@
 //! This module has all the nasty stuff that I need to fill via template. It
 //! keeps it out of main. However, as a consequence, all the day modules need to
 //! be children of it.
+//!
+//! These days the nasty stuff is just the list of day modules. Each module
+//! registers its own `PUZZLE`, and `days!` turns the list into `mod`
+//! declarations plus the `REGISTRY`. This year's list also has the past years,
+//! which get a file like this one apiece.
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-
-pub const DAYS: &[&str] = &["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
-
-/// Maps puzzle identifiers to implementation functions. The main thing you
-/// gotta add when starting a new day.
-pub fn puzzle_fn(p: &str) -> fn(&str) -> Result<String, anyhow::Error> {
-    match p {
-        "1-1" => day1::part1,
-        "1-2" => day1::part2,
-        "2-1" => day2::part1,
-        "2-2" => day2::part2,
-        "3-1" => day3::part1,
-        "3-2" => day3::part2,
-        "4-1" => day4::part1,
-        "4-2" => day4::part2,
-        "5-1" => day5::part1,
-        "5-2" => day5::part2,
-        "6-1" => day6::part1,
-        "6-2" => day6::part2,
-        "7-1" => day7::part1,
-        "7-2" => day7::part2,
-        "8-1" => day8::part1,
-        "8-2" => day8::part2,
-        "9-1" => day9::part1,
-        "9-2" => day9::part2,
-        "10-1" => day10::part1,
-        "10-2" => day10::part2,
+crate::solution::days! {
+    day1,
+    day2,
+    day3,
+    day4,
+    day5,
+    day6,
+    day7,
+    day8,
+    day9,
+    day10,
+}
 
-        _ => panic!("That's not a valid puzzle yet"),
-    }
+crate::solution::years! {
 }
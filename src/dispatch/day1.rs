@@ -1,60 +1,80 @@
-// let's do a standard interface: dayN::partM(&str) -> String
-// I figure most answers will probably be ints? but can't fully predict.
-
-/// Apply the supplied rotations to a wrapping 100-tick dial (labeled 0-99), and
-/// count how many times the dial stops at 0.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    // dial starts at 50
-    let mut dial: i32 = 50;
-    let mut zero_counter: u32 = 0;
-
-    for rot in input.lines() {
-        // oh right, gotta pipe err handling further back...
-        let i = parse_rot_i32(rot).unwrap();
-        // println!("rotating {}...", i);
-        dial += i;
-        dial = wrap100(dial);
-        // println!("dial: {}", dial);
-        if dial == 0 {
-            zero_counter += 1;
-        }
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::{Line, ParseError, numbered_lines};
+
+pub struct Day1;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day1>();
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Secret Entrance";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, TEST_INPUTS, Answer::Int(3)),
+        Example::new(2, TEST_INPUTS, Answer::Int(6)),
+    ];
+
+    /// Signed rotations; left is negative.
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(numbered_lines(input)
+            .map(parse_rot_i32)
+            .collect::<Result<_, _>>()?)
     }
 
-    Ok(format!("{}", zero_counter))
-}
+    /// Apply the supplied rotations to a wrapping 100-tick dial (labeled 0-99), and
+    /// count how many times the dial stops at 0.
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        // dial starts at 50
+        let mut dial: i32 = 50;
+        let mut zero_counter: u32 = 0;
+
+        for &i in input {
+            // println!("rotating {}...", i);
+            dial += i;
+            dial = wrap100(dial);
+            // println!("dial: {}", dial);
+            if dial == 0 {
+                zero_counter += 1;
+            }
+        }
 
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let mut dial: i32 = 50;
-    let mut zero_counter: u32 = 0;
-
-    for rot in input.lines() {
-        let i = parse_rot_i32(rot).unwrap();
-        // println!("rotating {}...", i);
-        let (d, z) = turn_wrap_and_count_zeros(dial, i);
-        // println!("new dial: {}, zero crossings: {}", d, z);
-        dial = d;
-        zero_counter += z;
+        Ok(zero_counter.into())
     }
 
-    Ok(format!("{}", zero_counter))
-}
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut dial: i32 = 50;
+        let mut zero_counter: u32 = 0;
+
+        for &i in input {
+            // println!("rotating {}...", i);
+            let (d, z) = turn_wrap_and_count_zeros(dial, i);
+            // println!("new dial: {}, zero crossings: {}", d, z);
+            dial = d;
+            zero_counter += z;
+        }
 
-#[derive(PartialEq, Debug)]
-struct ParseError;
+        Ok(zero_counter.into())
+    }
+}
 
-fn parse_rot_i32(rot: &str) -> Result<i32, ParseError> {
-    let Some((dir, num)) = rot.trim().split_at_checked(1) else {
-        return Err(ParseError);
+fn parse_rot_i32(line: Line) -> Result<i32, ParseError> {
+    let rot = line.text.trim();
+    let Some((dir, num)) = rot.split_at_checked(1) else {
+        return Err(line.error(rot, "expected a rotation like `L68`"));
     };
     // requiring uppercase
     let signum = match dir {
         "R" => 1,
         "L" => -1,
         _ => {
-            return Err(ParseError);
+            return Err(line.error(dir, "expected `L` or `R`"));
         }
     };
-    let parsed_num = num.parse::<i32>().map_err(|_| ParseError)?;
+    let parsed_num = num
+        .parse::<i32>()
+        .map_err(|e| line.error(num, format!("expected a number of clicks ({e})")))?;
 
     Ok(signum * parsed_num)
 }
@@ -149,7 +169,6 @@ fn wrap_count_test() {
     // assert_eq!(wrap100_and_count_zeroes(0 - 5), (95, 0));
 }
 
-#[allow(dead_code)]
 const TEST_INPUTS: &str = "L68
 L30
 R48
@@ -164,12 +183,12 @@ L82
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(TEST_INPUTS).unwrap(), "3".to_string());
+    crate::solution::assert_examples::<Day1>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(TEST_INPUTS).unwrap(), "6".to_string());
+    crate::solution::assert_examples::<Day1>(2);
 }
 
 #[test]
@@ -183,9 +202,24 @@ fn mod100() {
 
 #[test]
 fn parser() {
-    assert_eq!(parse_rot_i32("L30"), Ok(-30));
-    assert_eq!(parse_rot_i32("L101"), Ok(-101));
-    assert_eq!(parse_rot_i32("R14"), Ok(14));
-    assert!(parse_rot_i32("r14").is_err());
-    assert!(parse_rot_i32("14").is_err());
+    let parse = |text| parse_rot_i32(Line { number: 1, text });
+    assert_eq!(parse("L30"), Ok(-30));
+    assert_eq!(parse("L101"), Ok(-101));
+    assert_eq!(parse("R14"), Ok(14));
+    assert!(parse("r14").is_err());
+    assert!(parse("14").is_err());
+}
+
+#[test]
+fn parse_error_test() {
+    let err = Day1::parse("L68\nL30\nR4x8\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3, column 2: expected a number of clicks (invalid digit found in string), at \"4x8\""
+    );
+    let err = Day1::parse("L68\n\nL30\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 1: expected a rotation like `L68`, but the line ends there"
+    );
 }
@@ -1,37 +1,65 @@
-use std::ops::Range;
-
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::cancel;
+use crate::util::log::{debug, trace};
+use crate::util::{CombinateIndicesRepeated, CombinateIndicesUnrepeated};
+use crate::util::{Line, ParseError, numbered_lines};
 use anyhow::anyhow;
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "7".to_string());
+    crate::solution::assert_examples::<Day10>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "33".to_string());
+    crate::solution::assert_examples::<Day10>(2);
 }
 
-/// Ignore `{joltage requirements}` and determine the fewest button presses
-/// needed to make the lights match the desired pattern.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let mut grand_total = 0_usize;
-    for machine in input.lines().filter_map(my_machine) {
-        grand_total += machine.brute_force_lights_button_counts()?;
+pub struct Day10;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day10>();
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Factory";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(7)),
+        Example::new(2, _EXAMPLE, Answer::Int(33)),
+    ];
+
+    type Input<'a> = Vec<Machine>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        // Blank lines are harmless, but anything else that doesn't parse is
+        // worth hearing about.
+        Ok(numbered_lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(my_machine)
+            .collect::<Result<_, _>>()?)
     }
 
-    Ok(format!("{grand_total}"))
-}
+    /// Ignore `{joltage requirements}` and determine the fewest button presses
+    /// needed to make the lights match the desired pattern.
+    fn part1(machines: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut grand_total = 0_usize;
+        for machine in machines {
+            grand_total += machine.brute_force_lights_button_counts()?;
+        }
 
-/// OK, now multiple button presses matter. Find minimum # of button presses to
-/// make the joltage levels match the joltage requirements. YIKES. Is bruting
-/// this out of the question?
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let mut grand_total = 0usize;
-    for machine in input.lines().filter_map(my_machine) {
-        grand_total += machine.brute_force_joltage_reqs()?;
+        Ok(grand_total.into())
+    }
+
+    /// OK, now multiple button presses matter. Find minimum # of button presses to
+    /// make the joltage levels match the joltage requirements. YIKES. Is bruting
+    /// this out of the question?
+    fn part2(machines: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut grand_total = 0usize;
+        for machine in machines {
+            grand_total += machine.brute_force_joltage_reqs()?;
+        }
+        Ok(grand_total.into())
     }
-    Ok(format!("{grand_total}"))
 }
 
 const _EXAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
@@ -59,15 +87,15 @@ fn bitbutton_test() {
 
 /// A bitlight has to be assembled in REVERSE binary place-order! Because the
 /// leftmost listed light has to correspond to a named light position of 0
-/// (rightmost in a binary number).
-fn bitlights(l_str: &str) -> anyhow::Result<u32> {
+/// (rightmost in a binary number). Errors with where the bad char is.
+fn bitlights(l_str: &str) -> Result<u32, usize> {
     let mut res = 0_u32;
-    for (i, c) in l_str.chars().enumerate() {
+    for (i, c) in l_str.char_indices() {
         let bit = match c {
             '.' => 0u32,
             '#' => 1u32,
             _ => {
-                return Err(anyhow!("parse err for bit char"));
+                return Err(i);
             }
         };
         res += bit << i;
@@ -102,7 +130,7 @@ fn bitcount_test() {
 }
 
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     desired_lights: u32,
     buttons: Vec<u32>,
     joltage_reqs: Vec<u32>,
@@ -119,6 +147,7 @@ impl Machine {
             let combinations =
                 CombinateIndicesUnrepeated::try_new(0..self.buttons.len(), num_presses)?;
             for index_list in combinations {
+                cancel::check()?;
                 let mut result = 0_u32;
                 for i in index_list {
                     result ^= self.buttons[i];
@@ -154,9 +183,11 @@ impl Machine {
 
         let mut testbed = vec![0_u32; self.joltage_reqs.len()];
         for num_presses in min_presses..=max_presses {
-            println!("trying {num_presses} presses...");
+            trace!("trying {num_presses} presses...");
             let combinations = CombinateIndicesRepeated::new(0..self.buttons.len(), num_presses);
             for index_list in combinations {
+                // This is the one that runs ~forever, so let the runner stop us.
+                cancel::check()?;
                 testbed.fill(0);
                 for i in index_list {
                     let mut button = self.buttons[i];
@@ -171,7 +202,7 @@ impl Machine {
                     }
                 }
                 if testbed == self.joltage_reqs {
-                    println!("holy heck we did it: {num_presses}");
+                    debug!("holy heck we did it: {num_presses}");
                     return Ok(num_presses);
                 }
             }
@@ -182,267 +213,85 @@ impl Machine {
 }
 
 /// (quiet guitar, increasing tension)
-fn my_machine(line: &str) -> Option<Machine> {
-    let mut stuff = line.split(' ');
-    let lights = stuff.next()?.strip_prefix('[')?.strip_suffix(']')?;
-    let desired_lights = bitlights(lights).unwrap();
+fn my_machine(line: Line) -> Result<Machine, ParseError> {
+    let mut stuff = line.text.split_whitespace();
+    let first = stuff.next().unwrap_or(line.end());
+    let lights = first
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or_else(|| line.error(first, "expected the lights first, like `[.##.]`"))?;
+    if lights.chars().count() > 32 {
+        return Err(line.error(lights, "that's more lights than fit in a u32"));
+    }
+    let desired_lights =
+        bitlights(lights).map_err(|i| line.error(&lights[i..], "expected `.` or `#`"))?;
     let mut buttons = Vec::<u32>::new();
     let mut joltage_reqs: Option<Vec<u32>> = None;
     for item in stuff {
-        match item.as_bytes()[0] {
-            b'(' => {
-                // button
-                let positions = item
-                    .strip_prefix('(')?
-                    .strip_suffix(')')?
-                    .split(',')
-                    .map(|d| d.parse::<u32>().expect("come on, man!!!"));
-                buttons.push(bitbutton(positions));
-            }
-            b'{' => {
-                joltage_reqs = item
-                    .strip_prefix('{')?
-                    .strip_suffix('}')?
-                    .split(',')
-                    .map(|d| d.parse::<u32>().ok())
-                    .collect();
+        if let Some(positions) = item.strip_prefix('(').and_then(|i| i.strip_suffix(')')) {
+            // button
+            let mut parsed = Vec::new();
+            for d in positions.split(',') {
+                let position = d
+                    .parse::<u32>()
+                    .map_err(|e| line.error(d, format!("expected a light number ({e})")))?;
+                // Also keeps bitbutton from shifting off the end.
+                if position as usize >= lights.len() {
+                    return Err(line.error(d, format!("there are only {} lights", lights.len())));
+                }
+                parsed.push(position);
             }
-            _ => {
-                return None;
+            buttons.push(bitbutton(parsed.into_iter()));
+        } else if let Some(reqs) = item.strip_prefix('{').and_then(|i| i.strip_suffix('}')) {
+            let mut parsed = Vec::new();
+            for d in reqs.split(',') {
+                parsed.push(
+                    d.parse::<u32>()
+                        .map_err(|e| line.error(d, format!("expected a joltage ({e})")))?,
+                );
             }
+            joltage_reqs = Some(parsed);
+        } else {
+            return Err(line.error(
+                item,
+                "expected a button like `(1,3)` or joltages like `{3,5,4,7}`",
+            ));
         }
     }
 
-    Some(Machine {
+    Ok(Machine {
         desired_lights,
         buttons,
-        joltage_reqs: joltage_reqs?,
+        joltage_reqs: joltage_reqs.ok_or_else(|| {
+            line.error(
+                line.end(),
+                "expected the joltage requirements last, like `{3,5,4,7}`",
+            )
+        })?,
     })
 }
 
-/// A lil iterator for doing like... "3 of 0..5" -> [0, 1, 2], [0, 1, 3], [0, 1, 4],
-/// [0, 2, 3], [0, 2, 4], [0, 3, 4], [1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]
-struct CombinateIndicesUnrepeated {
-    range: Range<usize>,
-    // state also encodes the number of elements, in its length.
-    state: Option<Vec<usize>>, // None for done
-}
-
-fn r_idx(range: &Range<usize>, i: usize) -> Option<usize> {
-    let n = range.start + i;
-    if n < range.end { Some(n) } else { None }
-}
-
-impl CombinateIndicesUnrepeated {
-    fn try_new(range: Range<usize>, num_elements: usize) -> anyhow::Result<Self> {
-        if num_elements > range.len() {
-            return Err(anyhow!(
-                "Can't combine {} elements of {} options",
-                num_elements,
-                range.len()
-            ));
-        }
-        // build initial state, with the first value we'll return
-        let mut state = Vec::<usize>::with_capacity(num_elements);
-        for n in 0..num_elements {
-            let i = r_idx(&range, n).ok_or(anyhow!("impossible, we just checked"))?;
-            state.push(i);
-        }
-
-        Ok(Self {
-            range,
-            state: Some(state),
-        })
-    }
-}
-
-impl Iterator for CombinateIndicesUnrepeated {
-    type Item = Vec<usize>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // max goes down with every leftward step through the state.
-        let mut beyond_max = self.range.end;
-        let Some(ref mut state) = self.state else {
-            // done.
-            return None;
-        };
-        // Grab our result,
-        let res = state.clone();
-        // then advance the state. Walk backwards, trying to bump the highest
-        // position we can get away with.
-        let state_len = state.len();
-        for position in (0..state_len).rev() {
-            let mut val = state[position] + 1;
-            if val < beyond_max {
-                // we're good! walk back forward and re-set affected
-                // positions to their new minimums.
-                for slot in &mut state[position..state_len] {
-                    *slot = val;
-                    val += 1;
-                }
-                return Some(res);
-            }
-            // we're not good! keep walking backward... and decrement our max.
-            beyond_max -= 1;
-        }
-        // If we managed to escape the for-loop without early returning... I
-        // think we're out of headroom, i.e. our initial state before trying
-        // this woulda been, e.g., [2, 3, 4] in the "3 of 0..5" case. So burn the ships.
-        self.state = None;
-        // and return our last value.
-        Some(res)
-    }
-}
-
 #[test]
-fn combinate_indices_unrepeating_test() {
-    let thing = CombinateIndicesUnrepeated::try_new(0..5, 3).unwrap();
-    let expect: Vec<Vec<usize>> = vec![
-        vec![0, 1, 2],
-        vec![0, 1, 3],
-        vec![0, 1, 4],
-        vec![0, 2, 3],
-        vec![0, 2, 4],
-        vec![0, 3, 4],
-        vec![1, 2, 3],
-        vec![1, 2, 4],
-        vec![1, 3, 4],
-        vec![2, 3, 4],
-    ];
-    let outcome: Vec<_> = thing.collect();
-    assert_eq!(outcome, expect);
-
-    let small = CombinateIndicesUnrepeated::try_new(0..5, 5).unwrap();
-    let expect: Vec<Vec<usize>> = vec![vec![0, 1, 2, 3, 4]];
-    let outcome: Vec<_> = small.collect();
-    assert_eq!(outcome, expect);
-
-    let wrong = CombinateIndicesUnrepeated::try_new(0..5, 6);
-    assert!(wrong.is_err());
-}
-
-/// Ok, so this one would turn 3 of 0..5 into uhhhhh
-/// 000, 001, 002, 003, 004,
-/// 011, 012, 013, 014,
-/// 022, 023, 024,
-/// 033, 034,
-/// 044,
-/// and, like the pattern we've been seeing, 110 is equivalent to 011, so,
-/// 111, 112, 113, 114,
-/// 122, 123, 124,
-/// 133, 134,
-/// 144,
-/// 222, 223, 224,
-/// 233, 234,
-/// 244,
-/// 333, 334,
-/// 344,
-/// 444
-struct CombinateIndicesRepeated {
-    range: Range<usize>,
-    state: Option<Vec<usize>>,
-}
-
-impl CombinateIndicesRepeated {
-    fn new(range: Range<usize>, num_elements: usize) -> Self {
-        // Oddly, there's no error state here. 6 of 0..5 could be [0,0,0,0,0,0].
-
-        // build initial state, with the first value we'll return
-        let state = vec![0_usize; num_elements];
-
-        Self {
-            range,
-            state: Some(state),
-        }
-    }
-}
-
-impl Iterator for CombinateIndicesRepeated {
-    type Item = Vec<usize>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // max goes down with every leftward step through the state.
-        let beyond_max = self.range.end;
-        let Some(ref mut state) = self.state else {
-            // done.
-            return None;
-        };
-        // Grab our result,
-        let res = state.clone();
-        // then advance the state. Walk backwards, trying to bump the highest
-        // position we can get away with.
-        let state_len = state.len();
-        for position in (0..state_len).rev() {
-            let val = state[position] + 1;
-            if val < beyond_max {
-                // we're good! walk back forward and re-set affected
-                // positions to their new minimums.
-                // THIS IS THE DIFFERENCE: the new forward minimum doesn't
-                // increase by position.
-                for slot in &mut state[position..state_len] {
-                    *slot = val;
-                    // val += 1;
-                }
-                return Some(res);
-            }
-            // we're not good! keep walking backward...
-            // ALSO I think we don't decrement our max this time.
-            // beyond_max -= 1;
-        }
-        // If we managed to escape the for-loop without early returning... I
-        // think we're out of headroom, i.e. our initial state before trying
-        // this woulda been, e.g., [4, 4, 4] in the "3 of 0..5" case. So burn the ships.
-        self.state = None;
-        // and return our last value.
-        Some(res)
-    }
-}
-
-#[test]
-fn combinate_indices_repeating_test() {
-    let thing = CombinateIndicesRepeated::new(0..5, 3);
-    let expect: Vec<Vec<usize>> = vec![
-        vec![0, 0, 0],
-        vec![0, 0, 1],
-        vec![0, 0, 2],
-        vec![0, 0, 3],
-        vec![0, 0, 4],
-        vec![0, 1, 1],
-        vec![0, 1, 2],
-        vec![0, 1, 3],
-        vec![0, 1, 4],
-        vec![0, 2, 2],
-        vec![0, 2, 3],
-        vec![0, 2, 4],
-        vec![0, 3, 3],
-        vec![0, 3, 4],
-        vec![0, 4, 4],
-        vec![1, 1, 1],
-        vec![1, 1, 2],
-        vec![1, 1, 3],
-        vec![1, 1, 4],
-        vec![1, 2, 2],
-        vec![1, 2, 3],
-        vec![1, 2, 4],
-        vec![1, 3, 3],
-        vec![1, 3, 4],
-        vec![1, 4, 4],
-        vec![2, 2, 2],
-        vec![2, 2, 3],
-        vec![2, 2, 4],
-        vec![2, 3, 3],
-        vec![2, 3, 4],
-        vec![2, 4, 4],
-        vec![3, 3, 3],
-        vec![3, 3, 4],
-        vec![3, 4, 4],
-        vec![4, 4, 4],
-    ];
-    let outcome: Vec<_> = thing.collect();
-    assert_eq!(outcome, expect);
-
-    let small = CombinateIndicesRepeated::new(0..1, 5);
-    let expect: Vec<Vec<usize>> = vec![vec![0, 0, 0, 0, 0]];
-    let outcome: Vec<_> = small.collect();
-    assert_eq!(outcome, expect);
+fn parse_error_test() {
+    let err = |input| Day10::parse(input).unwrap_err().to_string();
+    assert_eq!(
+        err("[.##.] (3) (1,3) {3,5,4,7}\n[.#x] (0) {1}\n"),
+        "line 2, column 4: expected `.` or `#`, at \"x\""
+    );
+    assert_eq!(
+        err("[.##.] (3) (1,4) {3,5,4,7}\n"),
+        "line 1, column 15: there are only 4 lights, at \"4\""
+    );
+    assert_eq!(
+        err("[.##.] (3) (1,3)\n"),
+        "line 1, column 17: expected the joltage requirements last, like `{3,5,4,7}`, \
+         but the line ends there"
+    );
+    assert_eq!(
+        err("[.##.] (3) <1,3> {3,5,4,7}\n"),
+        "line 1, column 12: expected a button like `(1,3)` or joltages like `{3,5,4,7}`, \
+         at \"<1,3>\""
+    );
+    // Blank lines don't count against you.
+    assert_eq!(Day10::parse("\n[.#] (1) {1}\n\n").unwrap().len(), 1);
 }
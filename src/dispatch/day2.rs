@@ -1,40 +1,62 @@
-use crate::util::parse_range;
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::log::trace;
+use crate::util::{numbered_lines, parse_line_range};
 use anyhow::anyhow;
 use std::ops::RangeInclusive;
 
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let sum = input
-        .split(',')
-        .map(|txt| parse_range(txt).expect("hey what the..."))
-        .map(process_range_part1)
-        .reduce(|acc, e| acc + e);
+pub struct Day2;
 
-    sum.map(|i| format!("{}", i)).ok_or(anyhow!("lol"))
-}
+pub const PUZZLE: Puzzle = Puzzle::of::<Day2>();
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Gift Shop";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(1227775554)),
+        Example::new(2, _EXAMPLE, Answer::Int(4174379265)),
+    ];
+
+    type Input<'a> = Vec<RangeInclusive<u64>>;
 
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let sum = input
-        .split(',')
-        .map(|txt| parse_range(txt).expect("hey what the..."))
-        .map(sum_repeaties_in_range)
-        .reduce(|acc, e| acc + e);
-    sum.map(|i| format!("{}", i)).ok_or(anyhow!("lol"))
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        let mut ranges = Vec::new();
+        for line in numbered_lines(input) {
+            for range in line.text.split(',') {
+                ranges.push(parse_line_range(line, range)?);
+            }
+        }
+        Ok(ranges)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let sum = input
+            .iter()
+            .cloned()
+            .map(process_range_part1)
+            .reduce(|acc, e| acc + e);
+
+        sum.map(Answer::from).ok_or(anyhow!("lol"))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let sum = input
+            .iter()
+            .cloned()
+            .map(sum_repeaties_in_range)
+            .reduce(|acc, e| acc + e);
+        sum.map(Answer::from).ok_or(anyhow!("lol"))
+    }
 }
 
 #[test]
 fn part1_test() {
-    assert_eq!(
-        part1(_EXAMPLE).expect("should ok"),
-        "1227775554".to_string()
-    );
+    crate::solution::assert_examples::<Day2>(1);
 }
 
 #[test]
 fn part_2_test() {
-    assert_eq!(
-        part2(_EXAMPLE).expect("should ok"),
-        "4174379265".to_string()
-    );
+    crate::solution::assert_examples::<Day2>(2);
 }
 
 const _EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
@@ -306,7 +328,7 @@ fn sum_repeaties_in_range(r: RangeInclusive<u64>) -> u64 {
 /// Returns the sum of the repeated sequence numbers (exactly two repetitions)
 /// within the given range.
 fn process_range_part1(r: RangeInclusive<u64>) -> u64 {
-    println!("testing {:?}", &r);
+    trace!("testing {:?}", &r);
     let start = *r.start();
     let end = *r.end();
     let mut repeat_seq = first_repeatable_digit_sequence_from(start);
@@ -321,7 +343,7 @@ fn process_range_part1(r: RangeInclusive<u64>) -> u64 {
             break;
         }
         repeat_seq += 1;
-        println!("  found: {}", v);
+        trace!("  found: {}", v);
     }
     sum
 }
@@ -700,6 +722,8 @@ fn digits_with_interval_test() {
 
 #[test]
 fn parse_range_test() {
+    use crate::util::parse_range;
+
     assert_eq!(
         parse_range("38593856-38593862").expect("works"),
         38593856..=38593862
@@ -708,3 +732,14 @@ fn parse_range_test() {
     assert!(parse_range("1234-").is_err());
     assert!(parse_range("-1234").is_err());
 }
+
+#[test]
+fn parse_error_test() {
+    let err = Day2::parse("11-22,95-115,998\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 1, column 14: expected a range like `5-8`, at \"998\""
+    );
+    let err = Day2::parse("11-22,95-1x5").unwrap_err();
+    assert!(err.to_string().starts_with("line 1, column 10: "), "{err}");
+}
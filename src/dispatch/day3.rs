@@ -1,25 +1,55 @@
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let mut working_bank = Vec::<u64>::with_capacity(100); // counted the line length.
-    let mut sum = 0_u64;
-    for line in input.lines() {
-        load_bank(&mut working_bank, line);
-        let res = process_bank_part1(&working_bank);
-        println!("res: {res}");
-        sum += res;
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::log::trace;
+use crate::util::{ParseError, numbered_lines};
+
+pub struct Day3;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day3>();
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Lobby";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(357)),
+        Example::new(2, _EXAMPLE, Answer::Int(3121910778619)),
+    ];
+
+    // Banks get loaded into a reused buffer one line at a time, so all there
+    // is to do up front is make sure they'll load. Whether they're long enough
+    // depends on the part.
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        check_digits(input)?;
+        Ok(input)
     }
-    Ok(format!("{}", sum))
-}
 
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let mut working_bank = Vec::<u64>::with_capacity(100); // counted the line length.
-    let mut sum = 0_u64;
-    for line in input.lines() {
-        load_bank(&mut working_bank, line);
-        let res = process_bank_part2(&working_bank, 12);
-        println!("res: {res}");
-        sum += res;
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        check_lengths(input, PART1_DIGITS)?;
+        let mut working_bank = Vec::<u64>::with_capacity(100); // counted the line length.
+        let mut sum = 0_u64;
+        for line in input.lines() {
+            load_bank(&mut working_bank, line);
+            let res = process_bank_part1(&working_bank);
+            trace!("res: {res}");
+            sum += res;
+        }
+        Ok(sum.into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        check_lengths(input, PART2_DIGITS)?;
+        let mut working_bank = Vec::<u64>::with_capacity(100); // counted the line length.
+        let mut sum = 0_u64;
+        for line in input.lines() {
+            load_bank(&mut working_bank, line);
+            let res = process_bank_part2(&working_bank, PART2_DIGITS);
+            trace!("res: {res}");
+            sum += res;
+        }
+        Ok(sum.into())
     }
-    Ok(format!("{}", sum))
 }
 
 const _EXAMPLE: &str = "987654321111111
@@ -30,15 +60,60 @@ const _EXAMPLE: &str = "987654321111111
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "357".to_string());
+    crate::solution::assert_examples::<Day3>(1);
 }
 
 #[test]
 fn part_2_test() {
+    crate::solution::assert_examples::<Day3>(2);
+}
+
+/// How many batteries each part turns on per bank, so every bank needs at
+/// least that many.
+const PART1_DIGITS: u32 = 2;
+const PART2_DIGITS: u32 = 12;
+
+fn check_digits(input: &str) -> Result<(), ParseError> {
+    for line in numbered_lines(input) {
+        if let Some(bad) = line.first_bad_char(|c| c.is_ascii_digit()) {
+            return Err(line.error(bad, "expected a bank of digits"));
+        }
+    }
+    Ok(())
+}
+
+fn check_lengths(input: &str, digits: u32) -> Result<(), ParseError> {
+    for line in numbered_lines(input) {
+        if line.text.len() < digits as usize {
+            return Err(line.error(
+                line.end(),
+                format!("expected a bank of at least {digits} batteries"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_error_test() {
+    let err = Day3::parse("987654321111111\n81111111x111119\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 9: expected a bank of digits, at \"x\""
+    );
+    // Plenty for part 1, but not for part 2.
+    let short = Day3::parse("987654321111111\n8111111\n").unwrap();
+    assert_eq!(
+        Day3::part1(&short, &Params::default()).unwrap(),
+        Answer::Int(98 + 81)
+    );
+    let err = Day3::part2(&short, &Params::default()).unwrap_err();
     assert_eq!(
-        part2(_EXAMPLE).expect("should ok"),
-        "3121910778619".to_string()
+        err.to_string(),
+        "line 2, column 8: expected a bank of at least 12 batteries, but the line ends there"
     );
+    let err = Day3::part1(&"5\n", &Params::default()).unwrap_err();
+    assert!(err.to_string().contains("at least 2 batteries"), "{err}");
 }
 
 /// void: replaces the contents of the provided vec with numbers extracted from
@@ -46,7 +121,7 @@ fn part_2_test() {
 fn load_bank(dest: &mut Vec<u64>, line: &str) {
     let stuff = line
         .chars()
-        .map(|c| c.to_digit(10).expect("malformed input") as u64);
+        .map(|c| c.to_digit(10).expect("checked in parse") as u64);
     dest.clear();
     dest.extend(stuff);
 }
@@ -54,7 +129,7 @@ fn load_bank(dest: &mut Vec<u64>, line: &str) {
 /// Returns the biggest two-digit number that can be formed from a bank of
 /// digits (respecting order, but with any amount of space between them).
 fn process_bank_part1(bank: &[u64]) -> u64 {
-    process_bank_part2(bank, 2)
+    process_bank_part2(bank, PART1_DIGITS)
 }
 
 fn process_bank_part2(bank: &[u64], digits: u32) -> u64 {
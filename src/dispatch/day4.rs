@@ -1,33 +1,53 @@
-use crate::util::Grid;
-
-/// Find all *occupied* cells where fewer than four of the eight surrounding
-/// cells are occupied.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let grid = build_grid(input)?;
-    let all_roll_indices = grid.iter_occupied_indices();
-    let all_roll_neighbor_counts = all_roll_indices.map(|i| {
-        let coords = grid.coords(i);
-        grid.count_occupied_neighbors(coords)
-    });
-    let accessible_rolls_count = all_roll_neighbor_counts.filter(|c| *c < 4).count();
-    Ok(format!("{accessible_rolls_count}"))
-}
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::{Grid, Line, ParseError, numbered_lines};
 
-/// Iteratively count how many rolls of paper can be removed, according to the
-/// accessibility rules in part 1. Each iteration exposes more rolls.
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let mut grid = build_grid(input)?;
-    let mut count = 0;
+pub struct Day4;
 
-    loop {
-        let iteration_count = grid.evict_and_count();
-        if iteration_count == 0 {
-            break;
-        }
-        count += iteration_count;
+pub const PUZZLE: Puzzle = Puzzle::of::<Day4>();
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Printing Department";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(13)),
+        Example::new(2, _EXAMPLE, Answer::Int(43)),
+    ];
+
+    type Input<'a> = Grid<bool>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        build_grid(input)
     }
 
-    Ok(format!("{count}"))
+    /// Find all *occupied* cells where fewer than four of the eight surrounding
+    /// cells are occupied.
+    fn part1(grid: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let all_roll_indices = grid.iter_occupied_indices();
+        let all_roll_neighbor_counts = all_roll_indices.map(|i| {
+            let coords = grid.coords(i);
+            grid.count_occupied_neighbors(coords)
+        });
+        let accessible_rolls_count = all_roll_neighbor_counts.filter(|c| *c < 4).count();
+        Ok(accessible_rolls_count.into())
+    }
+
+    /// Iteratively count how many rolls of paper can be removed, according to the
+    /// accessibility rules in part 1. Each iteration exposes more rolls.
+    fn part2(grid: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut grid = grid.clone();
+        let mut count = 0;
+
+        loop {
+            let iteration_count = grid.evict_and_count();
+            if iteration_count == 0 {
+                break;
+            }
+            count += iteration_count;
+        }
+
+        Ok(count.into())
+    }
 }
 
 const _EXAMPLE: &str = "..@@.@@@@.
@@ -44,45 +64,78 @@ const _EXAMPLE: &str = "..@@.@@@@.
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "13".to_string());
+    crate::solution::assert_examples::<Day4>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "43".to_string());
+    crate::solution::assert_examples::<Day4>(2);
 }
 
 fn build_grid(ascii: &str) -> anyhow::Result<Grid<bool>> {
-    let width = width_of_ascii_grid(ascii);
-    let stuff = load_ascii_grid_to_vec_of_bools(ascii);
+    let width = width_of_ascii_grid(ascii)?;
+    let mut stuff = Vec::new();
+    for line in numbered_lines(ascii) {
+        load_ascii_row_to_bools(line, width, &mut stuff)?;
+    }
     Grid::try_new(width, stuff)
 }
 
-fn load_ascii_grid_to_vec_of_bools(input: &str) -> Vec<bool> {
-    let stuff = input.bytes().filter_map(|b| {
+/// Every row has to be as wide as the first, or the grid comes out skewed.
+fn load_ascii_row_to_bools(
+    line: Line,
+    width: usize,
+    out: &mut Vec<bool>,
+) -> Result<(), ParseError> {
+    for (i, b) in line.text.bytes().enumerate() {
         match b {
-            b'\n' => None,
-            b'@' => Some(true),
-            b'.' => Some(false),
-            _ => None, // shouldn't happen
+            b'@' => out.push(true),
+            b'.' => out.push(false),
+            _ => return Err(line.error(&line.text[i..], "expected `@` or `.`")),
         }
-    });
-    Vec::from_iter(stuff)
+    }
+    if line.text.len() != width {
+        // Either the extra cells, or nothing, at the end of a short row.
+        let at = line.text.get(width..).unwrap_or(line.end());
+        return Err(line.error(at, format!("expected {width} cells, like the first row")));
+    }
+    Ok(())
 }
 
-fn width_of_ascii_grid(input: &str) -> usize {
-    input
-        .lines()
-        .next()
-        .expect("empty string, just gonna panic early")
-        .len()
+fn width_of_ascii_grid(input: &str) -> Result<usize, ParseError> {
+    let first = numbered_lines(input).next().unwrap_or(Line {
+        number: 1,
+        text: input,
+    });
+    match first.text.len() {
+        0 => Err(first.error(first.end(), "expected a row of the grid")),
+        width => Ok(width),
+    }
 }
 
 #[test]
 fn width_test() {
-    assert_eq!(width_of_ascii_grid(_EXAMPLE), 10);
-    // assert_eq!(width_of_ascii_grid(""), 0); // nope
-    assert_eq!(width_of_ascii_grid("\n"), 0);
+    assert_eq!(width_of_ascii_grid(_EXAMPLE), Ok(10));
+    assert!(width_of_ascii_grid("").is_err());
+    assert!(width_of_ascii_grid("\n").is_err());
+}
+
+#[test]
+fn parse_error_test() {
+    // Grids don't Debug, so no unwrap_err.
+    let err = |input| build_grid(input).err().expect("should fail").to_string();
+    assert_eq!(
+        err("..@\n.@x\n"),
+        "line 2, column 3: expected `@` or `.`, at \"x\""
+    );
+    assert_eq!(
+        err("..@\n.@\n"),
+        "line 2, column 3: expected 3 cells, like the first row, but the line ends there"
+    );
+    assert_eq!(
+        err("..@\n.@..\n"),
+        "line 2, column 4: expected 3 cells, like the first row, at \".\""
+    );
 }
 
 #[test]
@@ -1,33 +1,55 @@
-use crate::util::parse_range;
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::log::{debug, trace};
+use crate::util::{numbered_lines, parse_line_range};
 use anyhow::anyhow;
 use std::{cmp::Ordering, ops::RangeInclusive};
 
-// Count how many ingredient IDs are in at least one fresh range.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let (ranges, ids) = parse_inputs(input)?;
-    let count = ids
-        .iter()
-        .filter(|&n| ranges.iter().any(|r| r.contains(n)))
-        .count();
+pub struct Day5;
 
-    Ok(format!("{count}"))
-}
+pub const PUZZLE: Puzzle = Puzzle::of::<Day5>();
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Cafeteria";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(3)),
+        Example::new(2, _EXAMPLE, Answer::Int(14)),
+    ];
 
-// Count how many possible fresh ingredient IDs are described by the ranges,
-// ignoring actual provided IDs and deduplicating overlapping ranges.
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let (ranges, _) = parse_inputs(input)?;
-    let initial_len = ranges.len();
-    let merged_ranges = compact_ranges(ranges);
-    let merged_len = merged_ranges.len();
-    println!("Reduced from {initial_len} to {merged_len}");
-    let count = merged_ranges
-        .iter()
-        .map(r_len)
-        .reduce(|a, b| a + b)
-        .ok_or(anyhow!("compacted list of ranges shouldn't be empty??"))?;
-
-    Ok(format!("{count}"))
+    type Input<'a> = (Vec<RangeInclusive<u64>>, Vec<u64>);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_inputs(input)
+    }
+
+    // Count how many ingredient IDs are in at least one fresh range.
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let (ranges, ids) = input;
+        let count = ids
+            .iter()
+            .filter(|&n| ranges.iter().any(|r| r.contains(n)))
+            .count();
+
+        Ok(count.into())
+    }
+
+    // Count how many possible fresh ingredient IDs are described by the ranges,
+    // ignoring actual provided IDs and deduplicating overlapping ranges.
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let ranges = input.0.clone();
+        let initial_len = ranges.len();
+        let merged_ranges = compact_ranges(ranges);
+        let merged_len = merged_ranges.len();
+        debug!("Reduced from {initial_len} to {merged_len}");
+        let count = merged_ranges
+            .iter()
+            .map(r_len)
+            .reduce(|a, b| a + b)
+            .ok_or(anyhow!("compacted list of ranges shouldn't be empty??"))?;
+
+        Ok(count.into())
+    }
 }
 
 // fresh ranges (inclusive), then available ids
@@ -46,29 +68,58 @@ const _EXAMPLE: &str = "3-5
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "3".to_string());
+    crate::solution::assert_examples::<Day5>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "14".to_string());
+    crate::solution::assert_examples::<Day5>(2);
 }
 
 /// Turn the day5 input string into numeric types
 fn parse_inputs(input: &str) -> anyhow::Result<(Vec<RangeInclusive<u64>>, Vec<u64>)> {
-    let (ranges_str, ids_str) = input
-        .split_once("\n\n")
-        .ok_or(anyhow!("input not a double-newline-separated list pair"))?;
+    let mut lines = numbered_lines(input);
+    let mut ranges = Vec::new();
+    // Ranges up to the blank line, IDs after it.
+    loop {
+        let line = lines
+            .next()
+            .ok_or(anyhow!("input not a double-newline-separated list pair"))?;
+        if line.text.is_empty() {
+            break;
+        }
+        ranges.push(parse_line_range(line, line.text)?);
+    }
 
     // Oh wow, Result<T: FromIterator, E> implements FromIterator! So you can
     // turn something that woulda been a Vec<Result<T, ...>> into a
     // Result<Vec<T>, ...>! Despite not being able to use question-mark in a
     // .map(). that's so awesome.
-    let ranges: anyhow::Result<Vec<RangeInclusive<u64>>> =
-        ranges_str.lines().map(parse_range).collect();
-    let ids: Result<Vec<u64>, std::num::ParseIntError> = ids_str.lines().map(str::parse).collect();
+    let ids: Result<Vec<u64>, _> = lines
+        .map(|line| {
+            line.text
+                .parse()
+                .map_err(|e| line.error(line.text, format!("expected an ingredient ID ({e})")))
+        })
+        .collect();
+
+    Ok((ranges, ids?))
+}
 
-    Ok((ranges?, ids?))
+#[test]
+fn parse_error_test() {
+    let err = parse_inputs("3-5\n10-14\n\n1\n5\n8x\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 6, column 1: expected an ingredient ID (invalid digit found in string), at \"8x\""
+    );
+    let err = parse_inputs("3-5\n10-\n\n1\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 4: expected a number (cannot parse integer from empty string), \
+         but the line ends there"
+    );
+    assert!(parse_inputs("3-5\n10-14\n").is_err());
 }
 
 /// what it sez
@@ -156,22 +207,22 @@ fn compact_ranges(mut input_ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclus
         .next()
         .expect("don't u play ding-dong-ditch with me young man");
 
-    println!("  in: {:?}", &current);
+    trace!("  in: {:?}", &current);
 
     for next in feed {
         if overlaps(&current, &next) {
-            println!("  in: {:?}", &next);
+            trace!("  in: {:?}", &next);
             current = merge(current, next);
         } else {
             // we hit a disjunction.
-            println!("merged: {:?}", &current);
-            println!("  in: {:?}", &next);
+            trace!("merged: {:?}", &current);
+            trace!("  in: {:?}", &next);
             output_ranges.push(current);
             current = next;
         }
     }
     // we're left with one dangling current range at the end.
-    println!("merged: {:?}", &current);
+    trace!("merged: {:?}", &current);
     output_ranges.push(current);
 
     output_ranges
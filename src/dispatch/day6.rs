@@ -1,3 +1,7 @@
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::log::trace;
+use crate::util::{Line, ParseError, numbered_lines};
 use anyhow::anyhow;
 use std::fmt::Display;
 use std::iter::Rev;
@@ -5,34 +9,57 @@ use std::ops::Add;
 use std::ops::Mul;
 use std::str::Chars;
 
-/// Solve all the columnar addition-or-multiplication problems, and sum the answers.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let mut muncher = LinearProblemMuncher::maybe_new(input).ok_or(anyhow!("not enough lines?"))?;
-    let mut storage = Vec::<u64>::new();
-    let mut sum = 0_u64;
-    while let Some(problem) = muncher.next_problem(storage) {
-        println!("{}", &problem);
-        sum += problem.solve();
-        storage = problem.recycle_storage();
+pub struct Day6;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day6>();
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Trash Compactor";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(4277556)),
+        Example::new(2, _EXAMPLE, Answer::Int(3263827)),
+    ];
+
+    // The two parts read the worksheet in completely different ways, so each
+    // one brings its own muncher.
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        check_worksheet(input)?;
+        Ok(input)
     }
-    Ok(format!("{sum}"))
-}
 
-/// omfg gotta throw it all away. Now columnar position of digits matters.
-/// Derive the *vertically written* numbers and try again.
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let mut muncher = RTLColumnarProblemMuncher::maybe_new(input)
-        .ok_or(anyhow!("Must not have had enough lines??"))?;
-    let mut storage = Vec::<u64>::new();
-    let mut sum = 0_u64;
-
-    while let Some(problem) = muncher.next_problem(storage) {
-        println!("{}", &problem);
-        sum += problem.solve();
-        storage = problem.recycle_storage();
+    /// Solve all the columnar addition-or-multiplication problems, and sum the answers.
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut muncher =
+            LinearProblemMuncher::maybe_new(input).ok_or(anyhow!("not enough lines?"))?;
+        let mut storage = Vec::<u64>::new();
+        let mut sum = 0_u64;
+        while let Some(problem) = muncher.next_problem(storage) {
+            trace!("{}", &problem);
+            sum += problem.solve();
+            storage = problem.recycle_storage();
+        }
+        Ok(sum.into())
     }
 
-    Ok(format!("{sum}"))
+    /// omfg gotta throw it all away. Now columnar position of digits matters.
+    /// Derive the *vertically written* numbers and try again.
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut muncher = RTLColumnarProblemMuncher::maybe_new(input)
+            .ok_or(anyhow!("Must not have had enough lines??"))?;
+        let mut storage = Vec::<u64>::new();
+        let mut sum = 0_u64;
+
+        while let Some(problem) = muncher.next_problem(storage) {
+            trace!("{}", &problem);
+            sum += problem.solve();
+            storage = problem.recycle_storage();
+        }
+
+        Ok(sum.into())
+    }
 }
 
 // Looks like they all stay positive. 🤗
@@ -46,12 +73,62 @@ const _EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "4277556".to_string());
+    crate::solution::assert_examples::<Day6>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "3263827".to_string());
+    crate::solution::assert_examples::<Day6>(2);
+}
+
+/// Rows of numbers, then a row of operators, all the same width. The munchers
+/// would just stop early on anything else and hand back a wrong answer.
+fn check_worksheet(input: &str) -> Result<(), ParseError> {
+    let lines: Vec<Line> = numbered_lines(input).collect();
+    let Some((operators, numbers)) = lines.split_last() else {
+        return Ok(());
+    };
+    for line in numbers {
+        if let Some(bad) = line.first_bad_char(|c| c.is_ascii_digit() || c == ' ') {
+            return Err(line.error(bad, "expected digits and spaces"));
+        }
+    }
+    if let Some(bad) = operators.first_bad_char(|c| matches!(c, '+' | '*' | ' ')) {
+        return Err(operators.error(bad, "expected the last line to be `+`, `*`, and spaces"));
+    }
+    // Part 2 reads down the columns, so they'd better line up.
+    let width = lines[0].text.len();
+    for line in &lines[1..] {
+        if line.text.len() != width {
+            let at = line.text.get(width..).unwrap_or(line.end());
+            return Err(line.error(
+                at,
+                format!(
+                    "expected {width} columns like line 1 (did an editor strip trailing spaces?)"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_error_test() {
+    // Exactly what the editor does to it.
+    let stripped = "123 328  51 64 \n 45 64  387 23\n  6 98  215 314\n*   +   *   +  \n";
+    assert_eq!(
+        Day6::parse(stripped).unwrap_err().to_string(),
+        "line 2, column 15: expected 15 columns like line 1 (did an editor strip trailing \
+         spaces?), but the line ends there"
+    );
+    assert_eq!(
+        Day6::parse("123 3x8\n*   +  \n").unwrap_err().to_string(),
+        "line 1, column 6: expected digits and spaces, at \"x\""
+    );
+    assert_eq!(
+        Day6::parse("123 328\n*   -  \n").unwrap_err().to_string(),
+        "line 2, column 5: expected the last line to be `+`, `*`, and spaces, at \"-\""
+    );
 }
 
 type OpFn = fn(u64, u64) -> u64;
@@ -1,10 +1,50 @@
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::{ParseError, numbered_lines};
 use anyhow::anyhow;
 
-/// How many times does the beam split, starting from its origin point?
-/// remember they can reconverge.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let state = shared_impl(input)?;
-    Ok(format!("{}", state.split_events))
+pub struct Day7;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day7>();
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Laboratories";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(21)),
+        Example::new(2, _EXAMPLE, Answer::Int(40)),
+    ];
+
+    // Both parts run the same simulation and just read different totals off
+    // the end of it.
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        check_manifold(input)?;
+        Ok(input)
+    }
+
+    /// How many times does the beam split, starting from its origin point?
+    /// remember they can reconverge.
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let state = shared_impl(input)?;
+        Ok(state.split_events.into())
+    }
+
+    /// How many paths could a single particle take through the forest of splitters?
+    /// uhhhhh...
+    ///
+    /// So, if we take the first three rows with splitters from the example as the
+    /// whole deal, we get 8. So, what would be overlapping beams keep getting
+    /// tracked separately -- it's six exits from the third row, but the middle
+    /// splitter's outputs count twice.
+    ///
+    /// If we add the fourth row... is that 13?? yeah. So, I think it's almost like
+    /// overlapping beams have *more weight.* Let's go with that and see.
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let state = shared_impl(input)?;
+        Ok(state.total_world_lines().into())
+    }
 }
 
 fn shared_impl(input: &str) -> anyhow::Result<BeamState> {
@@ -17,21 +57,6 @@ fn shared_impl(input: &str) -> anyhow::Result<BeamState> {
     Ok(state)
 }
 
-/// How many paths could a single particle take through the forest of splitters?
-/// uhhhhh...
-///
-/// So, if we take the first three rows with splitters from the example as the
-/// whole deal, we get 8. So, what would be overlapping beams keep getting
-/// tracked separately -- it's six exits from the third row, but the middle
-/// splitter's outputs count twice.
-///
-/// If we add the fourth row... is that 13?? yeah. So, I think it's almost like
-/// overlapping beams have *more weight.* Let's go with that and see.
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    let state = shared_impl(input)?;
-    Ok(format!("{}", state.total_world_lines()))
-}
-
 const _EXAMPLE: &str = ".......S.......
 ...............
 .......^.......
@@ -52,12 +77,53 @@ const _EXAMPLE: &str = ".......S.......
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "21".to_string());
+    crate::solution::assert_examples::<Day7>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "40".to_string());
+    crate::solution::assert_examples::<Day7>(2);
+}
+
+/// A start line with an `S` in it, then empty space and splitters, every line
+/// as wide as the first (`advance` indexes straight into them).
+fn check_manifold(input: &str) -> Result<(), ParseError> {
+    let mut lines = numbered_lines(input);
+    let Some(first) = lines.next() else {
+        return Ok(());
+    };
+    if let Some(bad) = first.first_bad_char(|c| c == '.' || c == 'S') {
+        return Err(first.error(bad, "expected the first line to be `.` and `S`"));
+    }
+    if !first.text.contains('S') {
+        return Err(first.error(first.text, "expected an `S` for the beam to start from"));
+    }
+    let width = first.text.len();
+    for line in lines {
+        if let Some(bad) = line.first_bad_char(|c| c == '.' || c == '^') {
+            return Err(line.error(bad, "expected `.` and `^`"));
+        }
+        if line.text.len() != width {
+            let at = line.text.get(width..).unwrap_or(line.end());
+            return Err(line.error(at, format!("expected {width} columns like line 1")));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_error_test() {
+    assert_eq!(
+        Day7::parse("...S...\n.......\n..^.^\n")
+            .unwrap_err()
+            .to_string(),
+        "line 3, column 6: expected 7 columns like line 1, but the line ends there"
+    );
+    assert_eq!(
+        Day7::parse("...S...\n...v...\n").unwrap_err().to_string(),
+        "line 2, column 4: expected `.` and `^`, at \"v\""
+    );
+    assert!(Day7::parse(".......\n").is_err());
 }
 
 fn is_splitter(byte: u8) -> bool {
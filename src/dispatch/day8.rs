@@ -1,59 +1,89 @@
-use std::{collections::HashSet, num::ParseIntError};
-
-use crate::util::{Coords, Grid, Vec3};
-
-/// Connect the *1000* closest-together pairs of boxes to form some number of
-/// circuits. Find the sizes of the *three* largest circuits, and multiply them.
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    part1_real(input, 1000)
-}
-
-pub fn part1_real(input: &str, connect: usize) -> anyhow::Result<String> {
-    let points = load_points(input)?;
-    let grid = grid_of_all_distances(&points)?;
-    let connections = connection_pairs(&grid);
-    // Build circuits... for a while.
-    let mut circuits = Circuits::new();
-    for (_len, (left, right)) in connections.into_iter().take(connect) {
-        circuits.add_connection(points[left], points[right]);
+use std::collections::HashSet;
+
+use crate::answer::Answer;
+use crate::solution::{Example, Param, Params, Puzzle, Solution};
+use crate::util::{Coords, Grid, ParseError, Vec3, numbered_lines};
+use anyhow::anyhow;
+
+pub struct Day8;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day8>();
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Playground";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: 1,
+            input: _EXAMPLE,
+            answer: Answer::Int(40),
+            params: &[("connect", "10")],
+        },
+        Example::new(2, _EXAMPLE, Answer::Int(25272)),
+    ];
+    const PARAMS: &'static [Param] = &[Param {
+        name: "connect",
+        default: "1000",
+        help: "how many of the closest pairs to connect in part 1",
+        range: Some(1..=i64::MAX),
+    }];
+
+    type Input<'a> = Vec<Vec3>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(load_points(input)?)
     }
-    // Sort em, multiply the three biggest lengths
-    circuits.sort_descending();
-
-    let product = circuits
-        .stuff
-        .iter()
-        .take(3)
-        .map(|set| set.len())
-        .reduce(|acc, e| acc * e)
-        .expect("Need at least 3 circuits");
-
-    Ok(format!("{product}"))
-}
 
-/// Connect nearest boxes until everything is on one circuit. Record the final
-/// connection, and get a checksum by multiplying the members' x coords.
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    // This time there's no divergent impl for test/real.
-    let points = load_points(input)?;
-    let grid = grid_of_all_distances(&points)?;
-    let connections = connection_pairs(&grid);
-
-    // Build circuits 'til done. Keep track of last actual work.
-    let mut circuits = Circuits::new();
-    let mut last_connection = (Vec3::default(), Vec3::default());
-    for (_len, (left_i, right_i)) in connections {
-        let (left, right) = (points[left_i], points[right_i]);
-        let result = circuits.add_connection(left, right);
-        if result {
-            last_connection = (left, right);
+    /// Connect the *1000* closest-together pairs of boxes to form some number of
+    /// circuits. Find the sizes of the *three* largest circuits, and multiply them.
+    fn part1(points: &Self::Input<'_>, params: &Params) -> anyhow::Result<Answer> {
+        let connect: usize = params.get("connect")?;
+        let grid = grid_of_all_distances(points)?;
+        let connections = connection_pairs(&grid);
+        // Build circuits... for a while.
+        let mut circuits = Circuits::new();
+        for (_len, (left, right)) in connections.into_iter().take(connect) {
+            circuits.add_connection(points[left], points[right]);
         }
+        // Sort em, multiply the three biggest lengths
+        circuits.sort_descending();
+
+        let product = circuits
+            .stuff
+            .iter()
+            .take(3)
+            .map(|set| set.len())
+            .reduce(|acc, e| acc * e)
+            .ok_or_else(|| {
+                anyhow!("connecting {connect} pairs (the `connect` param) made no circuits")
+            })?;
+
+        Ok(product.into())
     }
 
-    // get the checksum
-    let checksum = last_connection.0.x * last_connection.1.x;
+    /// Connect nearest boxes until everything is on one circuit. Record the final
+    /// connection, and get a checksum by multiplying the members' x coords.
+    fn part2(points: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        // This time there's no param for test/real.
+        let grid = grid_of_all_distances(points)?;
+        let connections = connection_pairs(&grid);
+
+        // Build circuits 'til done. Keep track of last actual work.
+        let mut circuits = Circuits::new();
+        let mut last_connection = (Vec3::default(), Vec3::default());
+        for (_len, (left_i, right_i)) in connections {
+            let (left, right) = (points[left_i], points[right_i]);
+            let result = circuits.add_connection(left, right);
+            if result {
+                last_connection = (left, right);
+            }
+        }
 
-    Ok(format!("{checksum}"))
+        // get the checksum
+        let checksum = last_connection.0.x * last_connection.1.x;
+
+        Ok(checksum.into())
+    }
 }
 
 const _EXAMPLE: &str = "162,817,812
@@ -79,23 +109,29 @@ const _EXAMPLE: &str = "162,817,812
 ";
 
 // hey notably: this only takes 10 shortest, and the real thing takes 1000. So
-// we need diverging impls.
+// the example has to say so in its params.
 #[test]
 fn part1_test() {
-    assert_eq!(
-        part1_real(_EXAMPLE, 10).expect("should ok"),
-        "40".to_string()
-    );
+    crate::solution::assert_examples::<Day8>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "25272".to_string());
+    crate::solution::assert_examples::<Day8>(2);
 }
 
-fn load_points(input: &str) -> Result<Vec<Vec3>, ParseIntError> {
+fn load_points(input: &str) -> Result<Vec<Vec3>, ParseError> {
     // hahahahahahaha hell yeah
-    input.lines().map(Vec3::from_str).collect()
+    numbered_lines(input).map(Vec3::from_line).collect()
+}
+
+#[test]
+fn load_points_test() {
+    let err = load_points("162,817,812\n57,618\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 7: expected 3 numbers separated by commas, but the line ends there"
+    );
 }
 
 /// The resulting grid contains (squared) distances. The scale of the X and Y
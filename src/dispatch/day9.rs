@@ -1,36 +1,56 @@
-use std::num::ParseIntError;
-
-use crate::util::{Coords, Grid, Vec2};
+use crate::answer::Answer;
+use crate::solution::{Example, Params, Puzzle, Solution};
+use crate::util::log::trace;
+use crate::util::{Coords, Grid, Vec2, numbered_lines};
 use anyhow::anyhow;
 
-pub fn part1(input: &str) -> Result<String, anyhow::Error> {
-    let stuff: Result<Vec<Vec2>, ParseIntError> = input.lines().map(Vec2::from_str).collect();
-    let stuff = stuff?;
-    let mut combinations = Vec::<i64>::with_capacity(stuff.len() * stuff.len() / 2);
-    for i in 0..stuff.len() {
-        if i + 1 >= stuff.len() {
-            break;
-        }
-        for j in (i + 1)..stuff.len() {
-            let h = stuff[i];
-            let w = stuff[j];
-            let diff = h - w;
-            let area = (diff.x.abs() + 1) * (diff.y.abs() + 1);
-            println!("{h} x {w}: area {area}");
-            combinations.push(area);
-        }
+pub struct Day9;
+
+pub const PUZZLE: Puzzle = Puzzle::of::<Day9>();
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Movie Theater";
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(1, _EXAMPLE, Answer::Int(50)),
+        Example::new(2, _EXAMPLE, Answer::Int(24)),
+    ];
+
+    type Input<'a> = Vec<Vec2>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(numbered_lines(input)
+            .map(Vec2::from_line)
+            .collect::<Result<_, _>>()?)
     }
-    let max = combinations
-        .iter()
-        .copied()
-        .max()
-        .ok_or(anyhow!("empty combinations??"))?;
 
-    Ok(format!("{max}"))
-}
+    fn part1(stuff: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        let mut combinations = Vec::<i64>::with_capacity(stuff.len() * stuff.len() / 2);
+        for i in 0..stuff.len() {
+            if i + 1 >= stuff.len() {
+                break;
+            }
+            for j in (i + 1)..stuff.len() {
+                let h = stuff[i];
+                let w = stuff[j];
+                let diff = h - w;
+                let area = (diff.x.abs() + 1) * (diff.y.abs() + 1);
+                trace!("{h} x {w}: area {area}");
+                combinations.push(area);
+            }
+        }
+        let max = combinations
+            .iter()
+            .copied()
+            .max()
+            .ok_or(anyhow!("empty combinations??"))?;
+
+        Ok(max.into())
+    }
 
-pub fn part2(input: &str) -> Result<String, anyhow::Error> {
-    Err(anyhow!("not implemented"))
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> anyhow::Result<Answer> {
+        Err(anyhow!("not implemented"))
+    }
 }
 
 const _EXAMPLE: &str = "7,1
@@ -45,12 +65,21 @@ const _EXAMPLE: &str = "7,1
 
 #[test]
 fn part1_test() {
-    assert_eq!(part1(_EXAMPLE).expect("should ok"), "50".to_string());
+    crate::solution::assert_examples::<Day9>(1);
 }
 
 #[test]
 fn part2_test() {
-    assert_eq!(part2(_EXAMPLE).expect("should ok"), "24".to_string());
+    crate::solution::assert_examples::<Day9>(2);
+}
+
+#[test]
+fn parse_error_test() {
+    let err = Day9::parse("7,1\n11,1\n11;7\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3, column 1: expected a number (invalid digit found in string), at \"11;7\""
+    );
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
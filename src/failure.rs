@@ -0,0 +1,111 @@
+//! Everything that can go wrong in the runner, sorted by whose fault it is, so
+//! the exit code can tell a wrapping script what happened.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum Failure {
+    /// Bad command line, including asking for a puzzle that doesn't exist.
+    Usage(String),
+    /// The puzzle input isn't where we looked for it.
+    NoInput {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// It ran fine, but the ledger says the answer is wrong.
+    WrongAnswer,
+    /// It ran past `--timeout`, so we stopped waiting.
+    Timeout { id: String, after: Duration },
+    /// We submitted an answer, but the site didn't say whether it's right:
+    /// too soon after the last one, an already-solved part, or a page we
+    /// couldn't make sense of. Holds the verdict.
+    Unjudged(String),
+    /// Anything else: the implementation errored or panicked, or we couldn't
+    /// read or write one of our own files.
+    Crash(anyhow::Error),
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::Crash(_) => ExitCode::from(1),
+            Failure::Usage(_) => ExitCode::from(2),
+            Failure::NoInput { .. } => ExitCode::from(3),
+            Failure::WrongAnswer => ExitCode::from(4),
+            Failure::Timeout { .. } => ExitCode::from(5),
+            Failure::Unjudged(_) => ExitCode::from(6),
+        }
+    }
+
+    /// When a batch run hits several problems, the exit code reports the worst
+    /// one. A crash beats a timeout beats a wrong answer beats a missing input.
+    /// (Only `submit` leaves an answer unjudged, and it's never a batch.)
+    pub fn severity(&self) -> u8 {
+        match self {
+            Failure::Crash(_) => 4,
+            Failure::Timeout { .. } => 3,
+            Failure::WrongAnswer => 2,
+            Failure::NoInput { .. } | Failure::Unjudged(_) => 1,
+            Failure::Usage(_) => 0,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Usage(msg) => write!(f, "{msg}"),
+            Failure::NoInput { path, source } => {
+                write!(f, "couldn't read puzzle input {}: {source}", path.display())
+            }
+            Failure::WrongAnswer => write!(f, "wrong answer"),
+            Failure::Timeout { id, after } => write!(f, "puzzle {id} timed out after {after:.2?}"),
+            Failure::Unjudged(verdict) => write!(f, "the site didn't judge the answer ({verdict})"),
+            // Spell out the whole chain of contexts, but not the backtrace;
+            // that's noise for a wrong parse.
+            Failure::Crash(e) => {
+                write!(f, "{e}")?;
+                for cause in e.chain().skip(1) {
+                    write!(f, "\n  caused by: {cause}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<anyhow::Error> for Failure {
+    fn from(value: anyhow::Error) -> Self {
+        Failure::Crash(value)
+    }
+}
+
+#[test]
+fn exit_codes_are_distinct_test() {
+    let codes: Vec<ExitCode> = [
+        Failure::Usage(String::new()),
+        Failure::NoInput {
+            path: PathBuf::new(),
+            source: std::io::ErrorKind::NotFound.into(),
+        },
+        Failure::WrongAnswer,
+        Failure::Timeout {
+            id: "1-1".to_string(),
+            after: Duration::from_secs(1),
+        },
+        Failure::Crash(anyhow::anyhow!("oops")),
+        Failure::Unjudged("rate-limited".to_string()),
+    ]
+    .iter()
+    .map(Failure::exit_code)
+    .collect();
+    for (i, a) in codes.iter().enumerate() {
+        assert_ne!(*a, ExitCode::SUCCESS);
+        for b in &codes[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
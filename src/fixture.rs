@@ -0,0 +1,257 @@
+//! Examples that live in files instead of in the day module, for when the
+//! example text can't survive being pasted into a string literal (day 6's
+//! trailing spaces, which editors love to strip). `aoc25 example` writes them,
+//! and `--example` and the tests read them alongside the day's `EXAMPLES`.
+//!
+//! They go in `fixtures/dayN/`, as `P-N.txt` (the input, byte for byte),
+//! `P-N.answer`, and, if the example needs any params, `P-N.params` with a
+//! `NAME=VALUE` per line. P is the part number and N just counts up. Past
+//! years get a directory of their own, like `fixtures/2024/day5/`.
+
+use crate::answer::Answer;
+use crate::meta::{self, Plan};
+use crate::solution::{Puzzle, year_prefix};
+use anyhow::{Context, bail};
+use std::path::{Path, PathBuf};
+
+/// One example from `fixtures/`.
+#[derive(Debug)]
+pub struct Fixture {
+    pub part: u8,
+    /// Where the input lives, so failures can point at it.
+    pub path: PathBuf,
+    pub input: String,
+    pub answer: Answer,
+    pub params: Vec<(String, String)>,
+}
+
+/// Where fixtures live: in the source tree, since they're part of the tests.
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+fn day_dir(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(format!("{}day{day}", year_prefix(year)))
+}
+
+/// Every file in a day's fixture directory, fixture or not, for `unmake`.
+pub fn files(dir: &Path, year: u32, day: u32) -> anyhow::Result<Vec<PathBuf>> {
+    let day_dir = day_dir(dir, year, day);
+    let entries = match std::fs::read_dir(&day_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("couldn't list {}", day_dir.display())),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.with_context(|| format!("couldn't list {}", day_dir.display()))?;
+        if entry.file_type().is_ok_and(|t| t.is_file()) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Every fixture for a day, in part order. No directory means no fixtures.
+pub fn load(dir: &Path, year: u32, day: u32) -> anyhow::Result<Vec<Fixture>> {
+    let day_dir = day_dir(dir, year, day);
+    let entries = match std::fs::read_dir(&day_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("couldn't list {}", day_dir.display())),
+    };
+
+    let mut found = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("couldn't list {}", day_dir.display()))?
+            .path();
+        if let Some(key) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".txt"))
+            .and_then(parse_key)
+        {
+            found.push((key, path));
+        }
+    }
+    found.sort();
+
+    let mut fixtures = Vec::with_capacity(found.len());
+    for ((part, _), path) in found {
+        let input = read(&path)?;
+        let answer = read(&path.with_extension("answer"))?;
+        let Ok(answer) = answer
+            .strip_suffix('\n')
+            .unwrap_or(&answer)
+            .parse::<Answer>();
+        let params_path = path.with_extension("params");
+        let params = if params_path.exists() {
+            parse_params(&read(&params_path)?)
+                .with_context(|| format!("in {}", params_path.display()))?
+        } else {
+            Vec::new()
+        };
+        fixtures.push(Fixture {
+            part,
+            path,
+            input,
+            answer,
+            params,
+        });
+    }
+    Ok(fixtures)
+}
+
+/// An example from either place, borrowed.
+pub struct AnyExample<'a> {
+    pub input: &'a str,
+    pub answer: &'a Answer,
+    pub params: Vec<(&'a str, &'a str)>,
+}
+
+/// A part's examples: the day's own `EXAMPLES`, then the fixtures (as loaded
+/// by `load`). Either one alone is plenty.
+pub fn examples<'a>(puzzle: &'a Puzzle, fixtures: &'a [Fixture], part: u8) -> Vec<AnyExample<'a>> {
+    let own = puzzle
+        .examples
+        .iter()
+        .filter(|e| e.part == part)
+        .map(|e| AnyExample {
+            input: e.input,
+            answer: &e.answer,
+            params: e.params.to_vec(),
+        });
+    let saved = fixtures
+        .iter()
+        .filter(|x| x.part == part)
+        .map(|x| AnyExample {
+            input: &x.input,
+            answer: &x.answer,
+            params: x
+                .params
+                .iter()
+                .map(|(n, v)| (n.as_str(), v.as_str()))
+                .collect(),
+        });
+    own.chain(saved).collect()
+}
+
+/// Writes a new fixture and returns where its input went. Never overwrites:
+/// each one gets the next free number for its part.
+pub fn save(
+    dir: &Path,
+    year: u32,
+    day: u32,
+    part: u8,
+    input: &str,
+    answer: &Answer,
+    params: &[(String, String)],
+) -> anyhow::Result<PathBuf> {
+    let day_dir = day_dir(dir, year, day);
+    let n = (1..)
+        .find(|n| !day_dir.join(format!("{part}-{n}.txt")).exists())
+        .expect("we'll run out of disk before numbers");
+    let path = day_dir.join(format!("{part}-{n}.txt"));
+
+    // All or nothing, so a failure halfway can't leave an input without an
+    // answer lying around for the tests to trip over.
+    let mut create = vec![
+        (path.clone(), input.to_string()),
+        (path.with_extension("answer"), format!("{answer}\n")),
+    ];
+    if !params.is_empty() {
+        let params: String = params
+            .iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
+        create.push((path.with_extension("params"), params));
+    }
+    meta::apply(&Plan {
+        create,
+        ..Plan::default()
+    })?;
+    Ok(path)
+}
+
+/// `1-2` into part 1, number 2.
+fn parse_key(key: &str) -> Option<(u8, u32)> {
+    let (part, n) = key.split_once('-')?;
+    Some((part.parse().ok()?, n.parse().ok()?))
+}
+
+fn parse_params(text: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut params = Vec::new();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        let Some((name, value)) = line.split_once('=') else {
+            bail!("expected NAME=VALUE, got {line:?}");
+        };
+        params.push((name.to_string(), value.to_string()));
+    }
+    Ok(params)
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("couldn't read {}", path.display()))
+}
+
+#[test]
+fn save_and_load_test() {
+    let dir = crate::scratch::Scratch::new("fixtures");
+    // Trailing spaces, no final newline: the kind of thing that gets mangled.
+    let input = "123 328  51 64 \n 45 64  387 23 \n*   +   *   +  ";
+    let first = save(&dir, 2025, 6, 1, input, &Answer::Int(4277556), &[]).unwrap();
+    let params = [("connect".to_string(), "10".to_string())];
+    let ok = Answer::Text("ok".into());
+    let second = save(&dir, 2025, 6, 1, "x", &ok, &params).unwrap();
+    save(&dir, 2025, 6, 2, "y", &Answer::Int(2), &[]).unwrap();
+    assert_ne!(first, second);
+    assert_eq!(std::fs::read_to_string(&first).unwrap(), input);
+
+    save(&dir, 2024, 6, 1, "z", &Answer::Int(3), &[]).unwrap();
+    assert!(dir.join("2024/day6/1-1.txt").exists());
+
+    let fixtures = load(&dir, 2025, 6).unwrap();
+    assert_eq!(fixtures.len(), 3);
+    assert_eq!(fixtures[0].input, input);
+    assert_eq!(fixtures[0].answer, Answer::Int(4277556));
+    assert!(fixtures[0].params.is_empty());
+    assert_eq!(fixtures[1].answer, ok);
+    assert_eq!(fixtures[1].params, params);
+    assert_eq!(fixtures[2].part, 2);
+    assert!(load(&dir, 2025, 7).unwrap().is_empty());
+    assert_eq!(load(&dir, 2024, 6).unwrap().len(), 1);
+
+    // Someone's answer with no input: saving over it fails, and takes the
+    // new input back out with it.
+    let squatter = dir.join("day8/1-1.answer");
+    std::fs::create_dir_all(squatter.parent().unwrap()).unwrap();
+    std::fs::write(&squatter, "42\n").unwrap();
+    assert!(save(&dir, 2025, 8, 1, "x", &Answer::Int(1), &[]).is_err());
+    assert!(!dir.join("day8/1-1.txt").exists());
+    assert_eq!(std::fs::read_to_string(&squatter).unwrap(), "42\n");
+}
+
+/// The data-driven half: every fixture in the repo gets checked against
+/// whatever's registered for its day, variants included.
+#[test]
+fn fixtures_test() {
+    for puzzle in crate::solution::registry() {
+        for fixture in load(&fixtures_dir(), puzzle.year, puzzle.day).unwrap() {
+            let f = puzzle.part(fixture.part).expect("only parts 1 and 2");
+            let params = puzzle
+                .params(fixture.params.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+                .expect("known params");
+            let answer = f(&fixture.input, &params)
+                .unwrap_or_else(|e| panic!("{}: {e:#}", fixture.path.display()));
+            assert_eq!(
+                answer,
+                fixture.answer,
+                "{} for day {}",
+                fixture.path.display(),
+                puzzle.name()
+            );
+        }
+    }
+}
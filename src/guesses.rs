@@ -0,0 +1,255 @@
+//! Every answer we've submitted, and what the site thought of it, so `submit`
+//! can stop us from making the same wrong guess twice (which costs a minute
+//! of timeout apiece). Lives in `guesses.log` next to `inputs/`, a line per
+//! guess, oldest first: the puzzle, the verdict, and the answer, separated by
+//! tabs, like `5-2 too-high 81237`.
+//!
+//! Ids are ledger keys, so a variant's guesses count for the original too.
+
+use crate::answer::Answer;
+use crate::util::read_if_exists;
+use anyhow::{Context, anyhow, bail};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub const GUESSES_PATH: &str = "guesses.log";
+
+/// What the site said about a guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// It didn't look at the answer, because we'd guessed too recently.
+    RateLimited,
+    /// Already solved, or not unlocked yet.
+    WrongLevel,
+    /// A page we don't recognize. The message will have to do.
+    Unclear,
+}
+
+impl Verdict {
+    /// Whether it says the answer is wrong. Only those are worth refusing to
+    /// send again.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+const VERDICTS: &[(Verdict, &str)] = &[
+    (Verdict::Right, "right"),
+    (Verdict::Wrong, "wrong"),
+    (Verdict::TooHigh, "too-high"),
+    (Verdict::TooLow, "too-low"),
+    (Verdict::RateLimited, "rate-limited"),
+    (Verdict::WrongLevel, "wrong-level"),
+    (Verdict::Unclear, "unclear"),
+];
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (_, name) = VERDICTS
+            .iter()
+            .find(|(v, _)| v == self)
+            .expect("all listed");
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VERDICTS
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(v, _)| *v)
+            .ok_or_else(|| anyhow!("`{s}` isn't a verdict"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub puzzle: String,
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+pub struct History {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// A missing file is just no guesses yet; a malformed one is an error.
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let guesses = match read_if_exists(&path)? {
+            Some(text) => parse(&text)?,
+            None => Vec::new(),
+        };
+        Ok(Self { path, guesses })
+    }
+
+    pub fn guesses(&self, puzzle: &str) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| g.puzzle == puzzle)
+    }
+
+    /// Errors if there's no point sending this answer: we already know it's
+    /// wrong, or we've already got it right. Otherwise, returns warnings for
+    /// anything that makes it look doubtful, like being above an answer that
+    /// was too high.
+    pub fn check(&self, puzzle: &str, answer: &Answer) -> anyhow::Result<Vec<String>> {
+        if let Some(right) = self.guesses(puzzle).find(|g| g.verdict == Verdict::Right) {
+            bail!(
+                "{puzzle} is already solved: the answer was {}",
+                right.answer
+            );
+        }
+        if let Some(known) = self
+            .guesses(puzzle)
+            .find(|g| g.verdict.is_wrong() && g.answer == *answer)
+        {
+            bail!(
+                "already guessed {answer} for {puzzle}, and the verdict was {}",
+                known.verdict
+            );
+        }
+
+        let mut warnings = Vec::new();
+        let Some(n) = answer.as_i128() else {
+            return Ok(warnings);
+        };
+        let bound = |verdict| {
+            self.guesses(puzzle)
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.as_i128())
+        };
+        // Only the tightest bound on each side is worth mentioning.
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|&high| n >= high) {
+            warnings.push(format!("{n} isn't below {high}, which was too high"));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|&low| n <= low) {
+            warnings.push(format!("{n} isn't above {low}, which was too low"));
+        }
+        Ok(warnings)
+    }
+
+    /// Remember a guess, tacking it onto the end of the file.
+    pub fn record(
+        &mut self,
+        puzzle: &str,
+        answer: &Answer,
+        verdict: Verdict,
+    ) -> anyhow::Result<()> {
+        use std::io::Write;
+
+        let guess = Guess {
+            puzzle: puzzle.to_string(),
+            answer: answer.clone(),
+            verdict,
+        };
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line(&guess).as_bytes()))
+            .with_context(|| format!("couldn't write {}", self.path.display()))?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+fn line(guess: &Guess) -> String {
+    format!("{}\t{}\t{}\n", guess.puzzle, guess.verdict, guess.answer)
+}
+
+fn parse(text: &str) -> anyhow::Result<Vec<Guess>> {
+    let mut guesses = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut fields = line.splitn(3, '\t');
+        let (Some(puzzle), Some(verdict), Some(answer)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            bail!(
+                "{GUESSES_PATH} line {}: expected PUZZLE, VERDICT, and ANSWER",
+                i + 1
+            );
+        };
+        let verdict = verdict
+            .parse()
+            .with_context(|| format!("{GUESSES_PATH} line {}", i + 1))?;
+        let Ok(answer) = answer.parse();
+        guesses.push(Guess {
+            puzzle: puzzle.to_string(),
+            answer,
+            verdict,
+        });
+    }
+    Ok(guesses)
+}
+
+#[test]
+fn history_test() {
+    let dir = crate::scratch::Scratch::new("guesses");
+    let path = dir.join(GUESSES_PATH);
+    let mut history = History::load(&path).unwrap();
+    assert!(history.check("5-2", &Answer::Int(100)).unwrap().is_empty());
+    history
+        .record("5-2", &Answer::Int(100), Verdict::TooHigh)
+        .unwrap();
+    history
+        .record("5-2", &Answer::Int(20), Verdict::TooLow)
+        .unwrap();
+    history
+        .record("5-2", &Answer::Int(90), Verdict::TooHigh)
+        .unwrap();
+    history
+        .record("5-2", &Answer::Int(50), Verdict::RateLimited)
+        .unwrap();
+    history
+        .record("5-1", &Answer::Text("ABC".into()), Verdict::Wrong)
+        .unwrap();
+
+    // It all survives a round trip.
+    let history = History::load(&path).unwrap();
+    assert_eq!(history.guesses("5-2").count(), 4);
+    let err = history.check("5-2", &Answer::Int(100)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "already guessed 100 for 5-2, and the verdict was too-high"
+    );
+    assert!(history.check("5-1", &Answer::Text("ABC".into())).is_err());
+    // Rate limited means nobody looked, so it's fair game.
+    assert!(history.check("5-2", &Answer::Int(50)).unwrap().is_empty());
+    assert_eq!(
+        history.check("5-2", &Answer::Int(95)).unwrap(),
+        vec!["95 isn't below 90, which was too high".to_string()]
+    );
+    assert_eq!(
+        history.check("5-2", &Answer::Int(19)).unwrap(),
+        vec!["19 isn't above 20, which was too low".to_string()]
+    );
+    assert!(history.check("6-1", &Answer::Int(100)).unwrap().is_empty());
+}
+
+#[test]
+fn solved_test() {
+    let dir = crate::scratch::Scratch::new("solved");
+    let path = dir.join(GUESSES_PATH);
+    std::fs::write(&path, "5-2\tright\t81196\n").unwrap();
+    let history = History::load(&path).unwrap();
+    let err = history.check("5-2", &Answer::Int(1)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "5-2 is already solved: the answer was 81196"
+    );
+
+    std::fs::write(&path, "5-2\tmaybe\t81196\n").unwrap();
+    assert!(History::load(&path).is_err());
+}
@@ -0,0 +1,314 @@
+//! Just enough HTTP to talk to the puzzle site: one request per connection,
+//! headers in, status and body out. Plain `http://` goes over a TcpStream.
+//! std doesn't do TLS, so `https://` (which is what the real site wants) gets
+//! handed to `curl`, which everybody has anyway.
+
+use anyhow::{Context, anyhow, bail};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// A GET, or a POST if there's a body (which is always a form, for us).
+pub fn request(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> anyhow::Result<Response> {
+    if url.starts_with("https://") {
+        return curl(url, headers, body);
+    }
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| anyhow!("{url} should start with http:// or https://"))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+
+    let mut stream =
+        TcpStream::connect(&address).with_context(|| format!("couldn't connect to {host}"))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let method = if body.is_some() { "POST" } else { "GET" };
+    let mut head = format!("{method} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n");
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if let Some(body) = body {
+        head.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    head.push_str(body.unwrap_or(""));
+    stream
+        .write_all(head.as_bytes())
+        .with_context(|| format!("couldn't send the request to {host}"))?;
+
+    // Connection: close means the body runs until the server hangs up.
+    let mut raw = Vec::new();
+    stream
+        .read_to_end(&mut raw)
+        .with_context(|| format!("couldn't read the response from {host}"))?;
+    parse_response(&raw)
+}
+
+fn parse_response(raw: &[u8]) -> anyhow::Result<Response> {
+    // Bytes until the very end, since chunk sizes count bytes, and decoding
+    // early would throw them off wherever there's something that isn't UTF-8.
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("the response ended before its headers did"))?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+    let mut lines = head.lines();
+    // Like `HTTP/1.1 200 OK`.
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| anyhow!("that doesn't look like an HTTP response"))?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+    let body = if chunked {
+        String::from_utf8_lossy(&unchunk(body)?).into_owned()
+    } else {
+        String::from_utf8_lossy(body).into_owned()
+    };
+    Ok(Response { status, body })
+}
+
+/// Stitches a `Transfer-Encoding: chunked` body back together.
+fn unchunk(mut body: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    loop {
+        let end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| anyhow!("a chunked body got cut off"))?;
+        let (size, rest) = (String::from_utf8_lossy(&body[..end]), &body[end + 2..]);
+        // Chunk extensions, after a `;`, are a thing nobody uses.
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size =
+            usize::from_str_radix(size, 16).map_err(|_| anyhow!("bad chunk size {size:?}"))?;
+        if size == 0 {
+            return Ok(out);
+        }
+        let Some(chunk) = rest.get(..size) else {
+            bail!("a chunked body got cut off");
+        };
+        out.extend_from_slice(chunk);
+        body = rest[size..].strip_prefix(b"\r\n").unwrap_or(&rest[size..]);
+    }
+}
+
+/// Escapes a value for a form body, like `a b&c` into `a+b%26c`.
+pub fn form_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// The https half. `-w` tacks the status code onto the end of the output,
+/// where we can pick it back off.
+fn curl(url: &str, headers: &[(&str, &str)], body: Option<&str>) -> anyhow::Result<Response> {
+    let (mut command, config) = curl_command(url, headers, body);
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("couldn't run curl, which is how we do https")?;
+    let mut stdin = child.stdin.take().expect("we asked for a pipe");
+    stdin.write_all(config.as_bytes())?;
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "curl couldn't get {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let out = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = out
+        .rsplit_once('\n')
+        .ok_or_else(|| anyhow!("curl didn't say how it went"))?;
+    let status = status
+        .parse()
+        .map_err(|_| anyhow!("curl gave a status of {status:?}"))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// The curl to run, and the config to feed it. The headers (our session
+/// cookie among them) and the body go in through stdin, since anybody on
+/// the machine can read a command line out of `ps`.
+fn curl_command(url: &str, headers: &[(&str, &str)], body: Option<&str>) -> (Command, String) {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--max-time", "30"])
+        .args(["--write-out", "\n%{http_code}"])
+        .args(["--config", "-"])
+        .arg(url);
+    let mut config = String::new();
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            config_quote(&format!("{name}: {value}"))
+        ));
+    }
+    if let Some(body) = body {
+        // data-raw, since data-binary would go looking for a file if a body
+        // ever started with `@`.
+        config.push_str(&format!("data-raw = {}\n", config_quote(body)));
+    }
+    (command, config)
+}
+
+/// Quotes a value for a curl config file, which has its own escapes.
+fn config_quote(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A stand-in server for the tests: answers one connection per canned
+/// `(status, body)`, in order, then hangs up for good. Joining the handle
+/// gets you the raw requests it saw.
+#[cfg(test)]
+pub fn serve(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            requests.push(read_request(&mut stream));
+            let response = format!(
+                "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
+
+/// The headers, plus however much body they promised.
+#[cfg(test)]
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut raw = Vec::new();
+    let mut byte = [0];
+    while !raw.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).unwrap();
+        raw.push(byte[0]);
+    }
+    let head = String::from_utf8(raw).unwrap();
+    let length = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Content-Length: "))
+        .map_or(0, |n| n.parse().unwrap());
+    let mut body = vec![0; length];
+    stream.read_exact(&mut body).unwrap();
+    head + &String::from_utf8(body).unwrap()
+}
+
+#[test]
+fn request_test() {
+    let (url, server) = serve(vec![(200, "hello\n"), (404, "nope")]);
+    let got = request(&format!("{url}/a/b"), &[("Cookie", "session=x")], None).unwrap();
+    assert_eq!((got.status, got.body.as_str()), (200, "hello\n"));
+    let got = request(&url, &[], Some("level=1&answer=2")).unwrap();
+    assert_eq!((got.status, got.body.as_str()), (404, "nope"));
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /a/b HTTP/1.1\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=x\r\n"));
+    assert!(requests[1].starts_with("POST / HTTP/1.1\r\n"));
+    assert!(requests[1].ends_with("\r\n\r\nlevel=1&answer=2"));
+}
+
+#[test]
+fn form_encode_test() {
+    assert_eq!(form_encode("-123"), "-123");
+    assert_eq!(form_encode("a b&c=d/é"), "a+b%26c%3Dd%2F%C3%A9");
+}
+
+#[test]
+fn parse_response_test() {
+    let chunked = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                   3\r\nabc\r\n5;x=y\r\nde\nfg\r\n0\r\n\r\n";
+    let response = parse_response(chunked.as_bytes()).unwrap();
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "abcde\nfg")
+    );
+    // A character split across chunks, and a byte that isn't UTF-8 at all,
+    // which used to throw the sizes of the chunks after it off.
+    let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                1\r\n\xC3\r\n2\r\n\xA9\xFF\r\n3\r\nabc\r\n0\r\n\r\n";
+    let response = parse_response(raw).unwrap();
+    assert_eq!(response.body, "é\u{FFFD}abc");
+    assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    assert!(parse_response(b"SSH-2.0-OpenSSH\r\n\r\n").is_err());
+}
+
+#[test]
+fn curl_command_test() {
+    let headers = [("Cookie", "session=cafe"), ("User-Agent", "a \"b\" \\c")];
+    let (command, config) = curl_command("https://x.test/1", &headers, Some("level=1&answer=2"));
+    // The session stays out of `ps`.
+    assert!(
+        command
+            .get_args()
+            .all(|arg| !arg.to_string_lossy().contains("cafe"))
+    );
+    assert_eq!(
+        config,
+        "header = \"Cookie: session=cafe\"\n\
+         header = \"User-Agent: a \\\"b\\\" \\\\c\"\n\
+         data-raw = \"level=1&answer=2\"\n"
+    );
+    let (_, config) = curl_command("https://x.test/1", &[], None);
+    assert!(config.is_empty());
+}
@@ -0,0 +1,255 @@
+//! Known-correct answers, so every run doubles as a regression check. Lives in
+//! `answers.toml` next to `inputs/`, as a flat table of puzzle ids to answers.
+//! Numbers are stored as numbers and everything else as strings:
+//!
+//! ```toml
+//! "1-1" = 1036
+//! "1-2" = 6228
+//! "13-2" = "HZLEHJRK"
+//! ```
+//!
+//! It's only a smidge of TOML, but it's the smidge we need, and I'm not pulling
+//! in a whole crate for it.
+
+use crate::answer::Answer;
+use crate::solution::CURRENT_YEAR;
+use crate::util::{read_if_exists, write_atomic};
+use anyhow::anyhow;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+pub const LEDGER_PATH: &str = "answers.toml";
+
+/// How a run's output compares to what we've recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer },
+    New,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail { .. } => write!(f, "FAIL"),
+            Status::New => write!(f, "NEW"),
+        }
+    }
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<String, Answer>,
+}
+
+impl Ledger {
+    /// A missing file is just an empty ledger; a malformed one is an error.
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let answers = match read_if_exists(&path)? {
+            Some(text) => parse(&text)?,
+            None => BTreeMap::new(),
+        };
+        Ok(Self { path, answers })
+    }
+
+    pub fn check(&self, puzzle: &str, output: &Answer) -> Status {
+        match self.answers.get(puzzle) {
+            None => Status::New,
+            Some(expected) if expected == output => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    /// Remember an answer and write the whole ledger back out.
+    pub fn record(&mut self, puzzle: &str, answer: &Answer) -> anyhow::Result<()> {
+        self.answers.insert(puzzle.to_string(), answer.clone());
+        write_atomic(&self.path, self.serialize())?;
+        Ok(())
+    }
+
+    /// Sorted in day order rather than string order, so 10 doesn't land
+    /// between 1 and 2. Past years (`2024/5-1`) go first, oldest first.
+    fn serialize(&self) -> String {
+        let mut entries: Vec<(&String, &Answer)> = self.answers.iter().collect();
+        entries.sort_by_key(|(puzzle, _)| sort_key(puzzle));
+        let mut out = String::from("# Known-correct answers. Managed by `aoc25 record`.\n");
+        for (puzzle, answer) in entries {
+            let value = match answer.as_i128() {
+                Some(n) => n.to_string(),
+                None => quote(&answer.to_string()),
+            };
+            out.push_str(&format!("{} = {}\n", quote(puzzle), value));
+        }
+        out
+    }
+}
+
+fn sort_key(puzzle: &str) -> (u32, u32, String) {
+    let (year, puzzle) = match puzzle.split_once('/') {
+        Some((year, rest)) => (year.parse().unwrap_or(u32::MAX), rest),
+        None => (CURRENT_YEAR, puzzle),
+    };
+    let digits: String = puzzle.chars().take_while(char::is_ascii_digit).collect();
+    let rest = puzzle[digits.len()..].to_string();
+    (year, digits.parse().unwrap_or(u32::MAX), rest)
+}
+
+fn parse(text: &str) -> anyhow::Result<BTreeMap<String, Answer>> {
+    let mut answers = BTreeMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or(anyhow!(
+            "{LEDGER_PATH} line {}: expected `key = value`",
+            i + 1
+        ))?;
+        let key =
+            unquote(key.trim()).ok_or(anyhow!("{LEDGER_PATH} line {}: malformed key", i + 1))?;
+        let value = parse_value(value.trim())
+            .ok_or(anyhow!("{LEDGER_PATH} line {}: malformed value", i + 1))?;
+        answers.insert(key, value);
+    }
+    Ok(answers)
+}
+
+/// Either an integer or a basic string.
+fn parse_value(s: &str) -> Option<Answer> {
+    if s.starts_with('"') {
+        let Ok(answer) = unquote(s)?.parse();
+        Some(answer)
+    } else {
+        s.parse::<i128>().ok().map(Answer::from)
+    }
+}
+
+/// Basic TOML string, escaping only what we could plausibly produce.
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Inverse of quote(). Also tolerates bare keys, since that's legal TOML and
+/// someone's gonna hand-edit this file.
+fn unquote(s: &str) -> Option<String> {
+    let Some(inner) = s.strip_prefix('"') else {
+        return Some(s.to_string());
+    };
+    let inner = inner.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '"' => out.push('"'),
+            '\\' => out.push('\\'),
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+#[test]
+fn quote_round_trip_test() {
+    for s in ["1036", "", "has \"quotes\"", "multi\nline\\grid"] {
+        assert_eq!(unquote(&quote(s)).as_deref(), Some(s));
+    }
+    assert_eq!(unquote("1-1").as_deref(), Some("1-1"));
+    assert_eq!(unquote("\"unterminated"), None);
+}
+
+#[test]
+fn parse_and_check_test() {
+    let text = "# comment\n\"1-1\" = 1036\n\n\"10-2\" = \"33\"\n\"13-2\" = \"#.\\n.#\"\n";
+    let ledger = Ledger {
+        path: PathBuf::new(),
+        answers: parse(text).unwrap(),
+    };
+    assert_eq!(ledger.check("1-1", &Answer::Int(1036)), Status::Pass);
+    assert_eq!(
+        ledger.check("1-1", &Answer::Int(1037)),
+        Status::Fail {
+            expected: Answer::Int(1036)
+        }
+    );
+    assert_eq!(ledger.check("10-2", &Answer::Int(33)), Status::Pass);
+    assert_eq!(
+        ledger.check("13-2", &Answer::Grid(vec!["#.".into(), ".#".into()])),
+        Status::Pass
+    );
+    assert_eq!(ledger.check("2-1", &Answer::from("whatever")), Status::New);
+
+    // A text answer with a leading zero survives being written out and read
+    // back, rather than turning into a number.
+    let mut ledger = ledger;
+    ledger.answers.insert("3-1".into(), Answer::from("0123"));
+    let answers = parse(&ledger.serialize()).unwrap();
+    assert!(matches!(&answers["3-1"], Answer::Text(t) if t == "0123"));
+    assert!(parse("\"1-1\" \"1036\"").is_err());
+    assert!(parse("\"1-1\" = bare words").is_err());
+}
+
+#[test]
+fn serialize_order_test() {
+    let mut answers = BTreeMap::new();
+    for p in ["10-1", "2024/10-1", "2-1", "1-2", "2024/2-1", "1-1"] {
+        answers.insert(p.to_string(), Answer::from("x"));
+    }
+    let ledger = Ledger {
+        path: PathBuf::new(),
+        answers,
+    };
+    let keys: Vec<String> = ledger
+        .serialize()
+        .lines()
+        .skip(1)
+        .map(|l| l.split(' ').next().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "\"2024/2-1\"",
+            "\"2024/10-1\"",
+            "\"1-1\"",
+            "\"1-2\"",
+            "\"2-1\"",
+            "\"10-1\""
+        ]
+    );
+}
+
+#[test]
+fn record_test() {
+    let dir = crate::scratch::Scratch::new("ledger-record");
+    let path = dir.join(LEDGER_PATH);
+    let mut ledger = Ledger::load(&path).unwrap();
+    ledger.record("1-1", &Answer::Int(1036)).unwrap();
+    ledger.record("1-2", &Answer::Int(6228)).unwrap();
+    let ledger = Ledger::load(&path).unwrap();
+    assert_eq!(ledger.check("1-1", &Answer::Int(1036)), Status::Pass);
+    assert_eq!(ledger.check("1-2", &Answer::Int(6228)), Status::Pass);
+    // No temp file left lying around.
+    assert_eq!(std::fs::read_dir(&*dir).unwrap().count(), 1);
+}
@@ -0,0 +1,23 @@
+//! Advent of Code 2025. The day modules live in `dispatch`, the stuff they
+//! share lives in `util`, and `runner` is the machinery for running them; the
+//! `aoc25` binary is just `cli` on top.
+
+pub mod answer;
+pub mod cli;
+pub mod diff;
+pub mod dispatch;
+pub mod failure;
+pub mod fixture;
+pub mod guesses;
+pub mod http;
+pub mod ledger;
+pub mod meta;
+pub mod pool;
+pub mod report;
+pub mod runner;
+#[cfg(test)]
+mod scratch;
+pub mod site;
+pub mod solution;
+pub mod util;
+pub mod watch;
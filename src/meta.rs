@@ -1,62 +1,807 @@
-/// Modify our own repository to create a new day's module and input file, and
-/// wire everything through main.rs appropriately. Make sure you do this in a
-/// clean workdir to avoid losing anything.
-pub fn new_day<'a>(day: &'a str) -> anyhow::Result<()> {
-    use std::io::Write;
-
-    let mut days = Vec::<&'a str>::new();
-    days.extend_from_slice(crate::dispatch::DAYS);
-    days.push(day);
-
-    // New impl module file (copy template)
-    std::fs::copy("./day.rs.template", format!("./src/dispatch/day{day}.rs"))?;
-    // New input file (empty)
-    let inputs = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .open(format!("./inputs/day{day}.txt"))?;
-    drop(inputs);
-    // Rewritten dispatch.rs (interpolate template)
-    let mut dispatch_file = std::fs::OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open("./src/dispatch.rs")?;
-    write!(
-        &mut dispatch_file,
-        include_str!("../dispatch.rs.template"),
-        day_mods = make_day_mods(&days)?,
-        puzzle_dispatches = make_puzzle_dispatches(&days)?,
-        days_list = make_days_list(&days)
-    )?;
+//! Modify our own repository to create a new day's module and input file, and
+//! wire it into dispatch.rs (or to take one back out). It's all-or-nothing: if
+//! any step fails, we put back whatever we'd done so far, so a failed `make`
+//! leaves the workdir the way it found it. Everything gets worked out as a
+//! `Plan` before we touch the tree, which also makes for a handy dry run.
+//!
+//! A day can also have variants, like `day11b.rs`, for trying out a rewrite
+//! without losing the original. They share the original's input.
+//!
+//! Past years get a tree of their own: `src/dispatch/y2024.rs` lists the
+//! modules in `src/dispatch/y2024/`, and their inputs go in `inputs/2024/`.
+//! This year's dispatch.rs lists the past years, too.
+//!
+//! The list of days comes from the files in `src/dispatch/`, not from the
+//! registry we were compiled with, since that goes stale the moment anybody
+//! adds a module by hand. `sync` rebuilds the dispatch files from those files
+//! and nothing else.
+
+use crate::fixture;
+use crate::runner::input_file;
+use crate::solution::{CURRENT_YEAR, parse_day, year_prefix};
+use anyhow::{Context, bail};
+use std::path::{Path, PathBuf};
+
+/// Everything `make`, `unmake`, or `sync` is going to do (or `example`, which
+/// only ever creates files).
+#[derive(Default)]
+pub struct Plan {
+    /// New files, and what goes in them.
+    pub create: Vec<(PathBuf, String)>,
+    pub remove: Vec<PathBuf>,
+    /// Existing files that get replaced wholesale, like dispatch.rs.
+    pub rewrite: Vec<Rewrite>,
+}
 
-    Ok(())
+pub struct Rewrite {
+    pub path: PathBuf,
+    pub old: String,
+    pub new: String,
 }
 
-/// `pub mod dayN;`
-fn make_day_mods(days: &[&str]) -> Result<String, std::fmt::Error> {
-    use std::fmt::Write;
+impl Plan {
+    pub fn changes_anything(&self) -> bool {
+        !self.create.is_empty()
+            || !self.remove.is_empty()
+            || self.rewrite.iter().any(|r| r.old != r.new)
+    }
 
-    let mut out = String::new();
-    for &day in days {
-        writeln!(&mut out, "pub mod day{day};")?;
+    /// What `--dry-run` prints: the doomed files, the new files, then diffs
+    /// of the regenerated dispatch files.
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        for path in &self.remove {
+            out.push_str(&format!("would delete {}\n", path.display()));
+        }
+        for (path, contents) in &self.create {
+            let lines = contents.lines().count();
+            out.push_str(&format!(
+                "would create {} ({lines} lines)\n",
+                path.display()
+            ));
+        }
+        for rewrite in &self.rewrite {
+            let name = rewrite.path.display().to_string();
+            let diff = crate::diff::unified(&rewrite.old, &rewrite.new, &name, &name);
+            if diff.is_empty() {
+                out.push_str(&format!("would leave {name} alone\n"));
+            } else {
+                out.push_str(&format!("would rewrite {name}:\n{diff}"));
+            }
+        }
+        out
     }
-    Ok(out)
+
+    /// Plans to write a whole file: a rewrite if it's there, or else a new
+    /// file.
+    fn write(&mut self, path: PathBuf, contents: String) -> anyhow::Result<()> {
+        if path.exists() {
+            let old = std::fs::read_to_string(&path)
+                .with_context(|| format!("couldn't read {}", path.display()))?;
+            self.rewrite.push(Rewrite {
+                path,
+                old,
+                new: contents,
+            });
+        } else {
+            self.create.push((path, contents));
+        }
+        Ok(())
+    }
+}
+
+/// The day module templates, by name. They're baked in at build time, so
+/// `make` works from anywhere.
+pub const TEMPLATES: &[(&str, &str)] = &[
+    ("plain", include_str!("../templates/plain.rs.template")),
+    ("grid", include_str!("../templates/grid.rs.template")),
+    ("lines", include_str!("../templates/lines.rs.template")),
+    (
+        "sections",
+        include_str!("../templates/sections.rs.template"),
+    ),
+];
+
+/// How `make` should fill in a new day.
+pub struct MakeOptions<'a> {
+    /// Empty for the day itself.
+    pub variant: &'a str,
+    /// One of the `TEMPLATES`.
+    pub template: &'a str,
+    pub title: &'a str,
 }
 
-/// The inner body of a match statement that produces function items.
-fn make_puzzle_dispatches(days: &[&str]) -> Result<String, std::fmt::Error> {
-    use std::fmt::Write;
+impl Default for MakeOptions<'_> {
+    fn default() -> Self {
+        Self {
+            variant: "",
+            template: "plain",
+            title: "???",
+        }
+    }
+}
+
+/// `crate_dir` is where `src/` lives, and `root` is where `inputs/` lives.
+pub fn new_day(
+    crate_dir: &Path,
+    root: &Path,
+    year: u32,
+    day: u32,
+    options: &MakeOptions,
+) -> anyhow::Result<()> {
+    apply(&plan_new_day(crate_dir, root, year, day, options)?)
+}
 
-    let mut out = String::new();
-    for &day in days {
-        writeln!(&mut out, r#"        "{day}-1" => day{day}::part1,"#)?;
-        writeln!(&mut out, r#"        "{day}-2" => day{day}::part2,"#)?;
+/// Works out what `new_day` would do, without doing it.
+pub fn plan_new_day(
+    crate_dir: &Path,
+    root: &Path,
+    year: u32,
+    day: u32,
+    options: &MakeOptions,
+) -> anyhow::Result<Plan> {
+    let variant = options.variant;
+    if !(2015..=CURRENT_YEAR).contains(&year) {
+        bail!("there wasn't an Advent of Code in {year}; it started in 2015");
+    }
+    if !(1..=25).contains(&day) {
+        bail!("day {day} isn't on the calendar; pick 1 through 25");
+    }
+    if !variant.chars().all(|c| c.is_ascii_lowercase()) {
+        bail!("variant `{variant}` should be lowercase letters, like `b`");
+    }
+    let name = format!("{}{day}{variant}", year_prefix(year));
+    let module = module_path(crate_dir, year, day, variant);
+    if module.exists() {
+        bail!("day {name} already exists; not overwriting it");
+    }
+    if !variant.is_empty() && !module_path(crate_dir, year, day, "").exists() {
+        bail!(
+            "day {}{day} doesn't exist, so there's nothing to make a variant of",
+            year_prefix(year)
+        );
+    }
+
+    let mut mods = scan_modules(crate_dir, year)?;
+    mods.push((day, variant.to_string()));
+
+    let Some(&(_, template)) = TEMPLATES.iter().find(|(name, _)| *name == options.template) else {
+        let names: Vec<&str> = TEMPLATES.iter().map(|(name, _)| *name).collect();
+        bail!(
+            "there's no `{}` template; try one of {}",
+            options.template,
+            names.join(", ")
+        );
+    };
+
+    let mut create = vec![(module, day_module(template, year, day, options))];
+    // New input file (empty), unless we've already got one.
+    let input = root.join(input_file(year, day));
+    if !input.exists() {
+        create.push((input, String::new()));
+    }
+    plan(crate_dir, year, create, Vec::new(), mods)
+}
+
+/// Deletes a day's module, and its input and fixtures too unless it's a
+/// variant, and takes it out of its dispatch file.
+pub fn unmake(
+    crate_dir: &Path,
+    root: &Path,
+    year: u32,
+    day: u32,
+    variant: &str,
+) -> anyhow::Result<()> {
+    apply(&plan_unmake(crate_dir, root, year, day, variant)?)
+}
+
+/// Works out what `unmake` would do, without doing it.
+pub fn plan_unmake(
+    crate_dir: &Path,
+    root: &Path,
+    year: u32,
+    day: u32,
+    variant: &str,
+) -> anyhow::Result<Plan> {
+    let name = format!("{}{day}{variant}", year_prefix(year));
+    let module = module_path(crate_dir, year, day, variant);
+    if !module.exists() {
+        bail!("day {name} doesn't exist");
+    }
+    let mut mods = scan_modules(crate_dir, year)?;
+    if variant.is_empty() {
+        // Variants without their original would have no input to share.
+        let variants: Vec<String> = mods
+            .iter()
+            .filter(|(d, v)| *d == day && !v.is_empty())
+            .map(|(d, v)| format!("{}{d}{v}", year_prefix(year)))
+            .collect();
+        if !variants.is_empty() {
+            bail!(
+                "day {name} still has variants ({}); unmake those first",
+                variants.join(", ")
+            );
+        }
+    }
+    mods.retain(|(d, v)| (*d, v.as_str()) != (day, variant));
+
+    let mut remove = vec![module];
+    let input = root.join(input_file(year, day));
+    if variant.is_empty() && input.exists() {
+        remove.push(input);
+    }
+    // Variants share the original's fixtures, like its input.
+    if variant.is_empty() {
+        remove.extend(fixture::files(&crate_dir.join("fixtures"), year, day)?);
+    }
+    plan(crate_dir, year, Vec::new(), remove, mods)
+}
+
+/// Works out what `sync` would do: regenerate every year's dispatch file to
+/// list exactly the day modules that exist.
+pub fn plan_sync(crate_dir: &Path) -> anyhow::Result<Plan> {
+    let mut plan = plan(
+        crate_dir,
+        CURRENT_YEAR,
+        Vec::new(),
+        Vec::new(),
+        scan_modules(crate_dir, CURRENT_YEAR)?,
+    )?;
+    for year in scan_years(crate_dir)? {
+        let (_, dispatch) = tree(crate_dir, year);
+        plan.write(
+            dispatch,
+            render_dispatch(&scan_modules(crate_dir, year)?, None),
+        )?;
+    }
+    Ok(plan)
+}
+
+/// Days that have a module but no input, and inputs that don't have a module,
+/// as messages for `sync` to pass along.
+pub fn mismatches(crate_dir: &Path, root: &Path) -> anyhow::Result<Vec<String>> {
+    let mut out = Vec::new();
+    for year in std::iter::once(CURRENT_YEAR).chain(scan_years(crate_dir)?) {
+        let prefix = year_prefix(year);
+        let modules = scan_modules(crate_dir, year)?;
+        let inputs = scan(&root.join(format!("inputs/{prefix}")), "day", ".txt")?;
+        for (day, _) in modules.iter().filter(|(_, v)| v.is_empty()) {
+            if !inputs.iter().any(|(d, v)| d == day && v.is_empty()) {
+                out.push(format!(
+                    "day {prefix}{day} has a module but no {}",
+                    input_file(year, *day).display()
+                ));
+            }
+        }
+        for (day, variant) in &inputs {
+            // Variants share the original's input, so they never have their own.
+            if !variant.is_empty() || !modules.iter().any(|(d, v)| d == day && v.is_empty()) {
+                out.push(format!(
+                    "inputs/{prefix}day{day}{variant}.txt doesn't go with any module"
+                ));
+            }
+        }
     }
     Ok(out)
 }
 
-/// `"1", "2", "3"` to be placed inside a slice literal.
-fn make_days_list(days: &[&str]) -> String {
-    let inner = days.join(r#"", ""#);
-    format!(r#""{inner}""#)
+/// Fills in the dispatch half of a plan, given the modules a year's dispatch
+/// file should list. A year we haven't seen before also needs adding to this
+/// year's dispatch.rs.
+fn plan(
+    crate_dir: &Path,
+    year: u32,
+    create: Vec<(PathBuf, String)>,
+    remove: Vec<PathBuf>,
+    mut mods: Vec<(u32, String)>,
+) -> anyhow::Result<Plan> {
+    mods.sort();
+    let mut plan = Plan {
+        create,
+        remove,
+        rewrite: Vec::new(),
+    };
+    let mut years = scan_years(crate_dir)?;
+    let (_, dispatch) = tree(crate_dir, year);
+    if year == CURRENT_YEAR {
+        plan.write(dispatch, render_dispatch(&mods, Some(&years)))?;
+        return Ok(plan);
+    }
+    plan.write(dispatch, render_dispatch(&mods, None))?;
+    if !years.contains(&year) {
+        years.push(year);
+        years.sort();
+        let current = scan_modules(crate_dir, CURRENT_YEAR)?;
+        let (_, dispatch) = tree(crate_dir, CURRENT_YEAR);
+        plan.write(dispatch, render_dispatch(&current, Some(&years)))?;
+    }
+    Ok(plan)
+}
+
+/// A dispatch file from the template. Only this year's lists the past years.
+fn render_dispatch(mods: &[(u32, String)], years: Option<&[u32]>) -> String {
+    let years = match years {
+        Some(years) => {
+            let lines: String = years.iter().map(|y| format!("    y{y},\n")).collect();
+            format!("\ncrate::solution::years! {{\n{lines}}}\n")
+        }
+        None => String::new(),
+    };
+    format!(
+        include_str!("../dispatch.rs.template"),
+        day_mods = make_day_mods(mods),
+        years = years
+    )
+}
+
+/// Where a year's day modules live, and the dispatch file that lists them.
+fn tree(crate_dir: &Path, year: u32) -> (PathBuf, PathBuf) {
+    let dispatch = crate_dir.join("src/dispatch");
+    match year {
+        CURRENT_YEAR => (dispatch, crate_dir.join("src/dispatch.rs")),
+        year => (
+            dispatch.join(format!("y{year}")),
+            dispatch.join(format!("y{year}.rs")),
+        ),
+    }
+}
+
+fn module_path(crate_dir: &Path, year: u32, day: u32, variant: &str) -> PathBuf {
+    tree(crate_dir, year)
+        .0
+        .join(format!("day{day}{variant}.rs"))
+}
+
+/// Every `dayN.rs` and `dayNb.rs` in a year's tree, in order.
+fn scan_modules(crate_dir: &Path, year: u32) -> anyhow::Result<Vec<(u32, String)>> {
+    scan(&tree(crate_dir, year).0, "day", ".rs")
+}
+
+/// The past years with a `yNNNN.rs` or a `yNNNN/` in `src/dispatch/`, oldest
+/// first.
+fn scan_years(crate_dir: &Path) -> anyhow::Result<Vec<u32>> {
+    let dir = crate_dir.join("src/dispatch");
+    let mut years: Vec<u32> = scan(&dir, "y", ".rs")?
+        .into_iter()
+        .chain(scan(&dir, "y", "")?)
+        .filter(|(year, variant)| variant.is_empty() && *year != CURRENT_YEAR)
+        .map(|(year, _)| year)
+        .collect();
+    years.sort();
+    years.dedup();
+    Ok(years)
+}
+
+/// Numbers and variants of the files in `dir` named like `day11b.txt`
+/// (for a prefix of `day` and an extension of `.txt`), sorted by number (so
+/// 2 comes before 10). Anything else in there is none of our business. A
+/// missing directory just means there aren't any.
+fn scan(dir: &Path, prefix: &str, extension: &str) -> anyhow::Result<Vec<(u32, String)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("couldn't list {}", dir.display())),
+    };
+    let mut found = Vec::new();
+    for entry in entries {
+        let entry = entry.with_context(|| format!("couldn't list {}", dir.display()))?;
+        let name = entry.file_name();
+        let Some((_, n, variant)) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix(prefix))
+            .and_then(|n| n.strip_suffix(extension))
+            // No slashes in a file name, so this never picks up a year.
+            .and_then(parse_day)
+        else {
+            continue;
+        };
+        found.push((n, variant.to_string()));
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// A template, filled in. A variant gets its own struct name, plus a
+/// `VARIANT` so the registry can tell it apart from the original, and a past
+/// year's day says which year it's from.
+fn day_module(template: &str, year: u32, day: u32, options: &MakeOptions) -> String {
+    let variant = options.variant;
+    let mut day_const = format!("const DAY: u32 = {day};");
+    if year != CURRENT_YEAR {
+        day_const = format!("const YEAR: u32 = {year};\n    {day_const}");
+    }
+    if !variant.is_empty() {
+        day_const.push_str(&format!(
+            "\n    const VARIANT: &'static str = \"{variant}\";"
+        ));
+    }
+    template
+        .replace("Day__DAY__", &format!("Day{day}{variant}"))
+        .replace("const DAY: u32 = __DAY__;", &day_const)
+        // Debug formatting does the quoting and escaping for us.
+        .replace("__TITLE__", &format!("{:?}", options.title))
+}
+
+/// Carries out a plan, or if something goes wrong, undoes what it did.
+pub fn apply(plan: &Plan) -> anyhow::Result<()> {
+    let mut scaffold = Scaffold::default();
+    let res = scaffold.build(plan);
+    if res.is_err() {
+        scaffold.roll_back();
+    }
+    res
+}
+
+/// Keeps track of what we've changed, so we can take it back.
+#[derive(Default)]
+struct Scaffold {
+    created: Vec<PathBuf>,
+    created_dirs: Vec<PathBuf>,
+    /// What the deleted files had in them.
+    removed: Vec<(PathBuf, Vec<u8>)>,
+    /// What the rewritten files had in them.
+    replaced: Vec<(PathBuf, String)>,
+}
+
+impl Scaffold {
+    fn build(&mut self, plan: &Plan) -> anyhow::Result<()> {
+        for path in &plan.remove {
+            self.remove(path)?;
+        }
+        for (path, contents) in &plan.create {
+            self.create(path, contents)?;
+        }
+        // Write each new file next door and rename it over the old one, so
+        // it's never half-written.
+        for rewrite in &plan.rewrite {
+            let mut temp = rewrite.path.clone().into_os_string();
+            temp.push(".tmp");
+            let temp = PathBuf::from(temp);
+            self.create(&temp, &rewrite.new)?;
+            std::fs::rename(&temp, &rewrite.path)
+                .with_context(|| format!("couldn't replace {}", rewrite.path.display()))?;
+            // It's not a temp file anymore, but the old contents might need
+            // to go back.
+            self.created.retain(|p| p != &temp);
+            self.replaced
+                .push((rewrite.path.clone(), rewrite.old.clone()));
+        }
+        Ok(())
+    }
+
+    /// Like fs::write, but refuses to clobber anything, and makes the
+    /// directory if need be.
+    fn create(&mut self, path: &Path, contents: &str) -> anyhow::Result<()> {
+        use std::io::Write;
+
+        if let Some(parent) = path.parent() {
+            self.create_dir(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("couldn't create {}", path.display()))?;
+        self.created.push(path.to_path_buf());
+        file.write_all(contents.as_bytes())
+            .with_context(|| format!("couldn't write {}", path.display()))?;
+        Ok(())
+    }
+
+    fn create_dir(&mut self, dir: &Path) -> anyhow::Result<()> {
+        if dir.as_os_str().is_empty() || dir.exists() {
+            return Ok(());
+        }
+        if let Some(parent) = dir.parent() {
+            self.create_dir(parent)?;
+        }
+        std::fs::create_dir(dir).with_context(|| format!("couldn't create {}", dir.display()))?;
+        self.created_dirs.push(dir.to_path_buf());
+        Ok(())
+    }
+
+    /// Like fs::remove_file, but hangs onto the contents first.
+    fn remove(&mut self, path: &Path) -> anyhow::Result<()> {
+        let contents =
+            std::fs::read(path).with_context(|| format!("couldn't read {}", path.display()))?;
+        std::fs::remove_file(path)
+            .with_context(|| format!("couldn't delete {}", path.display()))?;
+        self.removed.push((path.to_path_buf(), contents));
+        Ok(())
+    }
+
+    fn roll_back(&mut self) {
+        for (path, contents) in self.replaced.drain(..).rev() {
+            if let Err(e) = std::fs::write(&path, contents) {
+                eprintln!("couldn't put back {}: {e}", path.display());
+            }
+        }
+        for path in self.created.drain(..).rev() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                eprintln!("couldn't clean up {}: {e}", path.display());
+            }
+        }
+        for (path, contents) in self.removed.drain(..).rev() {
+            if let Err(e) = std::fs::write(&path, contents) {
+                eprintln!("couldn't put back {}: {e}", path.display());
+            }
+        }
+        for dir in self.created_dirs.drain(..).rev() {
+            if let Err(e) = std::fs::remove_dir(&dir) {
+                eprintln!("couldn't clean up {}: {e}", dir.display());
+            }
+        }
+    }
+}
+
+/// `    dayN,` lines, to be placed inside the `days!` invocation.
+fn make_day_mods(mods: &[(u32, String)]) -> String {
+    mods.iter()
+        .map(|(day, variant)| format!("    day{day}{variant},\n"))
+        .collect()
+}
+
+/// A scratch copy of the bits of the repo that `new_day` touches, with
+/// (empty) modules for days 1 through 10.
+#[cfg(test)]
+fn scratch_repo(name: &str) -> crate::scratch::Scratch {
+    let dir = crate::scratch::Scratch::new(&format!("meta-{name}"));
+    std::fs::create_dir_all(dir.join("src/dispatch")).unwrap();
+    std::fs::create_dir_all(dir.join("inputs")).unwrap();
+    std::fs::write(dir.join("src/dispatch.rs"), "// old\n").unwrap();
+    for day in 1..=10 {
+        std::fs::write(dir.join(format!("src/dispatch/day{day}.rs")), "").unwrap();
+    }
+    dir
+}
+
+#[test]
+fn new_day_test() {
+    let dir = scratch_repo("ok");
+    new_day(&dir, &dir, CURRENT_YEAR, 20, &MakeOptions::default()).unwrap();
+    let module = std::fs::read_to_string(dir.join("src/dispatch/day20.rs")).unwrap();
+    assert!(module.contains("pub struct Day20;"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("inputs/day20.txt")).unwrap(),
+        ""
+    );
+    let dispatch = std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap();
+    assert!(dispatch.contains("    day1,\n"));
+    assert!(dispatch.contains("    day20,\n"));
+    assert!(!dir.join("src/dispatch.rs.tmp").exists());
+
+    // Twice is once too many.
+    assert!(new_day(&dir, &dir, CURRENT_YEAR, 20, &MakeOptions::default()).is_err());
+}
+
+#[test]
+fn new_day_validates_test() {
+    let dir = scratch_repo("validate");
+    assert!(new_day(&dir, &dir, CURRENT_YEAR, 0, &MakeOptions::default()).is_err());
+    assert!(new_day(&dir, &dir, CURRENT_YEAR, 26, &MakeOptions::default()).is_err());
+    assert!(
+        new_day(&dir, &dir, CURRENT_YEAR, 1, &MakeOptions::default()).is_err(),
+        "day 1 already exists"
+    );
+    assert_eq!(
+        std::fs::read_dir(dir.join("src/dispatch")).unwrap().count(),
+        10
+    );
+}
+
+#[test]
+fn new_day_rolls_back_test() {
+    let dir = scratch_repo("rollback");
+    // Something squatting on the temp file makes the last step fail.
+    std::fs::write(dir.join("src/dispatch.rs.tmp"), "squatter").unwrap();
+    let err = new_day(&dir, &dir, CURRENT_YEAR, 21, &MakeOptions::default()).unwrap_err();
+    assert!(format!("{err:#}").contains("dispatch.rs.tmp"));
+    assert!(!dir.join("src/dispatch/day21.rs").exists());
+    assert!(!dir.join("inputs/day21.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap(),
+        "// old\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/dispatch.rs.tmp")).unwrap(),
+        "squatter"
+    );
+}
+
+#[test]
+fn dry_run_test() {
+    let dir = scratch_repo("dry-run");
+    let plan = plan_new_day(&dir, &dir, CURRENT_YEAR, 22, &MakeOptions::default()).unwrap();
+    let description = plan.describe();
+    assert!(description.contains("would create"));
+    assert!(description.contains("day22.rs"));
+    assert!(description.contains("-// old\n"));
+    assert!(description.contains("+    day22,\n"));
+    // ...and nothing happened.
+    assert!(!dir.join("src/dispatch/day22.rs").exists());
+    assert!(!dir.join("inputs/day22.txt").exists());
+}
+
+#[cfg(test)]
+fn variant(variant: &str) -> MakeOptions<'_> {
+    MakeOptions {
+        variant,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn variant_test() {
+    let dir = scratch_repo("variant");
+    new_day(&dir, &dir, CURRENT_YEAR, 8, &variant("b")).unwrap();
+    let module = std::fs::read_to_string(dir.join("src/dispatch/day8b.rs")).unwrap();
+    assert!(module.contains("pub struct Day8b;"));
+    assert!(module.contains("    const DAY: u32 = 8;\n    const VARIANT: &'static str = \"b\";\n"));
+    assert!(module.contains("assert_examples::<Day8b>(1)"));
+    let dispatch = std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap();
+    assert!(dispatch.contains("    day8,\n    day8b,\n    day9,\n"));
+
+    assert!(new_day(&dir, &dir, CURRENT_YEAR, 8, &variant("B")).is_err());
+    assert!(
+        new_day(&dir, &dir, CURRENT_YEAR, 23, &variant("b")).is_err(),
+        "no day 23 to be a variant of"
+    );
+}
+
+#[test]
+fn templates_test() {
+    let dir = scratch_repo("templates");
+    for (day, &(name, template)) in (11..).zip(TEMPLATES) {
+        // Everything day_module fills in has to be there to fill.
+        for placeholder in ["Day__DAY__", "const DAY: u32 = __DAY__;", "__TITLE__"] {
+            assert!(template.contains(placeholder), "{name} lacks {placeholder}");
+        }
+        let options = MakeOptions {
+            template: name,
+            title: "Say \"Cheese\"",
+            ..Default::default()
+        };
+        new_day(&dir, &dir, CURRENT_YEAR, day, &options).unwrap();
+        let module =
+            std::fs::read_to_string(dir.join(format!("src/dispatch/day{day}.rs"))).unwrap();
+        assert!(module.contains(r#"const TITLE: &'static str = "Say \"Cheese\"";"#));
+        assert!(!module.contains("__"), "{name} has a placeholder left over");
+    }
+    let options = MakeOptions {
+        template: "spreadsheet",
+        ..Default::default()
+    };
+    assert!(new_day(&dir, &dir, CURRENT_YEAR, 20, &options).is_err());
+}
+
+#[test]
+fn unmake_test() {
+    let dir = scratch_repo("unmake");
+    std::fs::write(dir.join("src/dispatch/day3.rs"), "// day 3\n").unwrap();
+    std::fs::write(dir.join("inputs/day3.txt"), "input\n").unwrap();
+    std::fs::create_dir_all(dir.join("fixtures/day3")).unwrap();
+    std::fs::write(dir.join("fixtures/day3/1-1.txt"), "example\n").unwrap();
+    std::fs::write(dir.join("fixtures/day3/1-1.answer"), "7\n").unwrap();
+    let description = plan_unmake(&dir, &dir, CURRENT_YEAR, 3, "")
+        .unwrap()
+        .describe();
+    assert!(description.contains("would delete"));
+    assert!(description.contains("day3.txt"));
+    assert!(description.contains("1-1.answer"));
+
+    unmake(&dir, &dir, CURRENT_YEAR, 3, "").unwrap();
+    assert!(!dir.join("src/dispatch/day3.rs").exists());
+    assert!(!dir.join("inputs/day3.txt").exists());
+    assert!(!dir.join("fixtures/day3/1-1.txt").exists());
+    assert!(!dir.join("fixtures/day3/1-1.answer").exists());
+    let dispatch = std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap();
+    assert!(dispatch.contains("    day2,\n    day4,\n"));
+
+    assert!(unmake(&dir, &dir, CURRENT_YEAR, 24, "").is_err());
+}
+
+#[test]
+fn unmake_rolls_back_test() {
+    let dir = scratch_repo("unmake-rollback");
+    std::fs::write(dir.join("src/dispatch/day3.rs"), "// day 3\n").unwrap();
+    std::fs::write(dir.join("inputs/day3.txt"), "input\n").unwrap();
+    std::fs::write(dir.join("src/dispatch.rs.tmp"), "squatter").unwrap();
+    assert!(unmake(&dir, &dir, CURRENT_YEAR, 3, "").is_err());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/dispatch/day3.rs")).unwrap(),
+        "// day 3\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("inputs/day3.txt")).unwrap(),
+        "input\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap(),
+        "// old\n"
+    );
+}
+
+#[test]
+fn sync_test() {
+    let dir = scratch_repo("sync");
+    // Some hand-made modules that dispatch.rs doesn't know about.
+    for name in ["day12.rs", "day3b.rs", "notes.rs"] {
+        std::fs::write(dir.join("src/dispatch").join(name), "").unwrap();
+    }
+    for name in ["day1.txt", "day13.txt"] {
+        std::fs::write(dir.join("inputs").join(name), "").unwrap();
+    }
+
+    let plan = plan_sync(&dir).unwrap();
+    assert!(plan.create.is_empty() && plan.remove.is_empty());
+    assert!(
+        plan.rewrite[0]
+            .new
+            .contains("    day3,\n    day3b,\n    day4,\n")
+    );
+    assert!(plan.rewrite[0].new.contains("    day10,\n    day12,\n}"));
+    assert!(!plan.rewrite[0].new.contains("notes"));
+    apply(&plan).unwrap();
+    assert!(!plan_sync(&dir).unwrap().changes_anything());
+
+    let mismatches = mismatches(&dir, &dir).unwrap();
+    assert!(mismatches.contains(&"day 12 has a module but no inputs/day12.txt".to_string()));
+    assert!(mismatches.contains(&"inputs/day13.txt doesn't go with any module".to_string()));
+    assert!(!mismatches.iter().any(|m| m.contains("day 1 ")));
+    assert!(!mismatches.iter().any(|m| m.contains("3b")));
+}
+
+#[test]
+fn past_year_test() {
+    let dir = scratch_repo("past-year");
+    new_day(&dir, &dir, 2024, 5, &MakeOptions::default()).unwrap();
+    let module = std::fs::read_to_string(dir.join("src/dispatch/y2024/day5.rs")).unwrap();
+    assert!(module.contains("    const YEAR: u32 = 2024;\n    const DAY: u32 = 5;\n"));
+    let year = std::fs::read_to_string(dir.join("src/dispatch/y2024.rs")).unwrap();
+    assert!(year.contains("    day5,\n"));
+    assert!(!year.contains("years!"));
+    let dispatch = std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap();
+    assert!(dispatch.contains("    day10,\n"));
+    assert!(dispatch.contains("crate::solution::years! {\n    y2024,\n}\n"));
+    assert!(dir.join("inputs/2024/day5.txt").exists());
+
+    // The second day that year leaves this year's dispatch.rs alone.
+    let plan = plan_new_day(&dir, &dir, 2024, 6, &MakeOptions::default()).unwrap();
+    assert_eq!(plan.rewrite.len(), 1);
+    apply(&plan).unwrap();
+    assert!(!plan_sync(&dir).unwrap().changes_anything());
+
+    unmake(&dir, &dir, 2024, 5, "").unwrap();
+    assert!(!dir.join("inputs/2024/day5.txt").exists());
+    std::fs::remove_file(dir.join("inputs/2024/day6.txt")).unwrap();
+    assert!(
+        mismatches(&dir, &dir)
+            .unwrap()
+            .contains(&"day 2024/6 has a module but no inputs/2024/day6.txt".to_string())
+    );
+    assert!(new_day(&dir, &dir, 1999, 5, &MakeOptions::default()).is_err());
+}
+
+#[test]
+fn past_year_rolls_back_test() {
+    let dir = scratch_repo("past-year-rollback");
+    std::fs::write(dir.join("src/dispatch.rs.tmp"), "squatter").unwrap();
+    assert!(new_day(&dir, &dir, 2023, 1, &MakeOptions::default()).is_err());
+    // Not even the directories it made for them.
+    assert!(!dir.join("src/dispatch/y2023").exists());
+    assert!(!dir.join("src/dispatch/y2023.rs").exists());
+    assert!(!dir.join("inputs/2023").exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/dispatch.rs")).unwrap(),
+        "// old\n"
+    );
+}
+
+#[test]
+fn make_day_mods_test() {
+    assert_eq!(
+        make_day_mods(&[(1, "".into()), (2, "".into()), (2, "b".into())]),
+        "    day1,\n    day2,\n    day2b,\n"
+    );
+    assert_eq!(make_day_mods(&[]), "");
 }
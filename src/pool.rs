@@ -0,0 +1,172 @@
+//! Running a batch of puzzle parts on a few threads at once, with a time limit
+//! on each so one runaway brute force can't hold up the whole report.
+//!
+//! Each part gets its own thread, and a fixed number of worker threads take
+//! turns waiting on them. We can't kill a thread, so if a part runs past its
+//! time limit, its worker cancels its token (see `util::cancel`) and moves
+//! along. A part that checks its token stops soon after; one that doesn't
+//! keeps burning CPU until the process exits.
+
+use crate::answer::Answer;
+use crate::runner::{run_part, timed};
+use crate::solution::{Params, PartFn};
+use crate::util::cancel::CancelToken;
+use anyhow::anyhow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// One part of one day, ready to go.
+pub struct Job {
+    pub id: String,
+    pub f: PartFn,
+    /// Shared between both parts of a day.
+    pub input: Arc<String>,
+    pub params: Params,
+}
+
+pub enum JobResult {
+    Done(anyhow::Result<Answer>, Duration),
+    TimedOut(Duration),
+}
+
+/// Runs every job on `workers` threads, and hands back the results in the
+/// same order as the jobs.
+pub fn run_jobs(jobs: &[Job], workers: usize, timeout: Option<Duration>) -> Vec<JobResult> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|s| {
+        for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+            let (tx, next) = (tx.clone(), &next);
+            s.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(i) else {
+                        break;
+                    };
+                    tx.send((i, run_job(job, timeout)))
+                        .expect("the receiver outlives the scope");
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    let mut results: Vec<Option<JobResult>> = jobs.iter().map(|_| None).collect();
+    for (i, result) in rx {
+        results[i] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|r| r.expect("every job reports back"))
+        .collect()
+}
+
+/// Runs one job on a thread of its own, if there's a time limit; otherwise,
+/// right here.
+pub fn run_job(job: &Job, timeout: Option<Duration>) -> JobResult {
+    let Some(timeout) = timeout else {
+        let (result, elapsed) = timed(|| run_part(&job.id, job.f, &job.input, &job.params));
+        return JobResult::Done(result, elapsed);
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let (id, f, input, params) = (job.id.clone(), job.f, job.input.clone(), job.params.clone());
+    let token = CancelToken::new();
+    let job_token = token.clone();
+    let start = Instant::now();
+    // Not scoped, since we might have to walk away from it.
+    std::thread::spawn(move || {
+        let res = job_token.install(|| timed(|| run_part(&id, f, &input, &params)));
+        let _ = tx.send(res);
+    });
+    match rx.recv_timeout(timeout) {
+        Ok((result, elapsed)) => JobResult::Done(result, elapsed),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            JobResult::TimedOut(start.elapsed())
+        }
+        // run_part catches panics, so this really shouldn't happen.
+        Err(RecvTimeoutError::Disconnected) => JobResult::Done(
+            Err(anyhow!("puzzle {} died without an answer", job.id)),
+            start.elapsed(),
+        ),
+    }
+}
+
+#[cfg(test)]
+fn job(id: &str, f: PartFn, input: &str) -> Job {
+    Job {
+        id: id.to_string(),
+        f,
+        input: Arc::new(input.to_string()),
+        params: Params::default(),
+    }
+}
+
+#[test]
+fn results_come_back_in_order_test() {
+    // Earlier jobs sleep longer, so they finish out of order.
+    let jobs: Vec<Job> = (0..8)
+        .map(|i| {
+            let f: PartFn = |input, _| {
+                let n: u64 = input.parse()?;
+                std::thread::sleep(Duration::from_millis(2 * (8 - n)));
+                Ok(n.into())
+            };
+            job(&format!("{i}-1"), f, &i.to_string())
+        })
+        .collect();
+    let results = run_jobs(&jobs, 3, None);
+    let answers: Vec<Answer> = results
+        .into_iter()
+        .map(|r| match r {
+            JobResult::Done(answer, _) => answer.unwrap(),
+            JobResult::TimedOut(_) => panic!("nobody should time out"),
+        })
+        .collect();
+    assert_eq!(answers, (0..8).map(Answer::Int).collect::<Vec<_>>());
+}
+
+#[test]
+fn runaway_times_out_test() {
+    let jobs = [
+        job(
+            "1-1",
+            |_, _| {
+                std::thread::sleep(Duration::from_secs(5));
+                Ok(Answer::Int(1))
+            },
+            "",
+        ),
+        job("1-2", |_, _| Ok(Answer::Int(2)), ""),
+    ];
+    let results = run_jobs(&jobs, 2, Some(Duration::from_millis(50)));
+    assert!(matches!(results[0], JobResult::TimedOut(_)));
+    assert!(matches!(&results[1], JobResult::Done(Ok(a), _) if *a == 2));
+}
+
+#[test]
+fn timeout_cancels_test() {
+    use std::sync::atomic::AtomicBool;
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+    let jobs = [job(
+        "1-1",
+        |_, _| loop {
+            if let Err(e) = crate::util::cancel::check() {
+                STOPPED.store(true, Ordering::Relaxed);
+                return Err(e);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        },
+        "",
+    )];
+    let results = run_jobs(&jobs, 1, Some(Duration::from_millis(20)));
+    assert!(matches!(results[0], JobResult::TimedOut(_)));
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !STOPPED.load(Ordering::Relaxed) {
+        assert!(Instant::now() < deadline, "the runaway never noticed");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,182 @@
+//! Machine-readable results, for things that would otherwise have to scrape
+//! the table. One JSON object per puzzle part:
+//!
+//! ```json
+//! {"year": 2025, "day": 1, "part": 1, "title": "Secret Entrance", "status": "PASS", "answer": "1036",
+//!  "expected": null, "duration_ms": 0.412, "error": null}
+//! ```
+//!
+//! `status` is one of PASS, FAIL, NEW, UNCHECKED (custom input or params, so
+//! the ledger doesn't apply), ERROR, TIMEOUT, or MISSING (no input file). Answers are
+//! always strings, since some are grids and some are too big for a double. `error`
+//! says what went wrong for an ERROR, or where we looked for a MISSING input:
+//!
+//! ```json
+//! {"year": 2025, "day": 2, "part": 1, "title": "Gift Shop", "status": "MISSING", "answer": null,
+//!  "expected": null, "duration_ms": null, "error": "no inputs/day2.txt"}
+//! ```
+
+use crate::answer::Answer;
+use crate::ledger::Status;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`; try `table` or `json`")),
+        }
+    }
+}
+
+/// How one puzzle part went.
+pub struct Report {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub title: &'static str,
+    pub outcome: Outcome,
+    /// None if it never ran.
+    pub duration: Option<Duration>,
+}
+
+pub enum Outcome {
+    /// Ran, and maybe got checked against the ledger.
+    Answered(Answer, Option<Status>),
+    Error(String),
+    /// Ran past `--timeout`, and we stopped waiting.
+    Timeout,
+    /// No input file where we looked, which was here.
+    Missing(PathBuf),
+}
+
+impl Report {
+    fn status(&self) -> String {
+        match &self.outcome {
+            Outcome::Answered(_, Some(status)) => status.to_string(),
+            Outcome::Answered(_, None) => "UNCHECKED".to_string(),
+            Outcome::Error(_) => "ERROR".to_string(),
+            Outcome::Timeout => "TIMEOUT".to_string(),
+            Outcome::Missing(_) => "MISSING".to_string(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let (answer, expected, error) = match &self.outcome {
+            Outcome::Answered(answer, status) => {
+                let expected = match status {
+                    Some(Status::Fail { expected }) => Some(expected.to_string()),
+                    _ => None,
+                };
+                (Some(answer.to_string()), expected, None)
+            }
+            Outcome::Error(e) => (None, None, Some(e.clone())),
+            Outcome::Timeout => (None, None, None),
+            Outcome::Missing(path) => (None, None, Some(format!("no {}", path.display()))),
+        };
+        let duration = self
+            .duration
+            .map(|d| format!("{:.3}", d.as_secs_f64() * 1000.0));
+        format!(
+            "{{\"year\": {}, \"day\": {}, \"part\": {}, \"title\": {}, \"status\": {}, \"answer\": {}, \"expected\": {}, \"duration_ms\": {}, \"error\": {}}}",
+            self.year,
+            self.day,
+            self.part,
+            json_string(self.title),
+            json_string(&self.status()),
+            json_or_null(answer.as_deref()),
+            json_or_null(expected.as_deref()),
+            duration.as_deref().unwrap_or("null"),
+            json_or_null(error.as_deref()),
+        )
+    }
+}
+
+/// A JSON array with one report per line, so it also greps okay.
+pub fn to_json(reports: &[Report]) -> String {
+    let items: Vec<String> = reports
+        .iter()
+        .map(|r| format!("  {}", r.to_json()))
+        .collect();
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    format!("[\n{}\n]", items.join(",\n"))
+}
+
+fn json_or_null(s: Option<&str>) -> String {
+    s.map(json_string).unwrap_or_else(|| "null".to_string())
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[test]
+fn json_string_test() {
+    assert_eq!(json_string("plain"), "\"plain\"");
+    assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+    assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+}
+
+#[test]
+fn report_json_test() {
+    let reports = [
+        Report {
+            year: 2025,
+            day: 1,
+            part: 2,
+            title: "Secret Entrance",
+            outcome: Outcome::Answered(
+                Answer::Int(6),
+                Some(Status::Fail {
+                    expected: Answer::Int(7),
+                }),
+            ),
+            duration: Some(Duration::from_micros(1500)),
+        },
+        Report {
+            year: 2025,
+            day: 2,
+            part: 1,
+            title: "Gift Shop",
+            outcome: Outcome::Missing("inputs/day2.txt".into()),
+            duration: None,
+        },
+    ];
+    let json = to_json(&reports);
+    let lines: Vec<&str> = json.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(
+        lines[1],
+        "  {\"year\": 2025, \"day\": 1, \"part\": 2, \"title\": \"Secret Entrance\", \"status\": \"FAIL\", \"answer\": \"6\", \"expected\": \"7\", \"duration_ms\": 1.500, \"error\": null},"
+    );
+    assert!(lines[2].contains("\"status\": \"MISSING\", \"answer\": null"));
+    assert!(lines[2].ends_with("\"error\": \"no inputs/day2.txt\"}"));
+    assert_eq!(to_json(&[]), "[]");
+}
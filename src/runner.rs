@@ -0,0 +1,518 @@
+//! The runner API: finding inputs, looking puzzles up, running them safely,
+//! and reporting on how it went. The CLI in `cli.rs` is a thin layer of arg
+//! parsing on top of this.
+
+use crate::answer::Answer;
+use crate::failure::Failure;
+use crate::fixture;
+use crate::ledger::{Ledger, Status};
+use crate::pool::{self, Job, JobResult};
+use crate::report::{self, Format, Outcome, Report};
+use crate::solution::{self, CURRENT_YEAR, Id, Params, PartFn, Puzzle};
+use anyhow::{Context, anyhow};
+use std::io::Read;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Where `inputs/` and the ledger live. That's the current directory if it
+/// looks like the repo, or else wherever the repo was when we got built, so
+/// the binary still works when you run it from somewhere else. `AOC25_ROOT`
+/// overrides both.
+pub fn workspace_root() -> PathBuf {
+    if let Some(root) = std::env::var_os("AOC25_ROOT") {
+        return root.into();
+    }
+    if Path::new("inputs").is_dir() {
+        return PathBuf::from(".");
+    }
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Where a day's input lives, relative to the workspace root: this year's
+/// right in `inputs/`, and past years' in subdirectories like `inputs/2024/`.
+pub fn input_file(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!(
+        "inputs/{}day{day}.txt",
+        solution::year_prefix(year)
+    ))
+}
+
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    workspace_root().join(input_file(year, day))
+}
+
+fn read_file(path: PathBuf) -> Result<String, Failure> {
+    std::fs::read_to_string(&path).map_err(|source| Failure::NoInput { path, source })
+}
+
+pub fn read_input(year: u32, day: u32) -> Result<String, Failure> {
+    read_file(input_path(year, day))
+}
+
+/// The input for a puzzle: from `--input` if we got one (`-` meaning stdin),
+/// and from the day's input file otherwise.
+pub fn input_string(p: &str, input_override: Option<&str>) -> Result<String, Failure> {
+    let id = parse_puzzle(p)?;
+    match input_override {
+        None => read_input(id.year, id.day),
+        Some(path) => read_path_or_stdin(path),
+    }
+}
+
+/// A file, or stdin if the path is `-`.
+pub fn read_path_or_stdin(path: &str) -> Result<String, Failure> {
+    if path != "-" {
+        return read_file(path.into());
+    }
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|source| Failure::NoInput {
+            path: "<stdin>".into(),
+            source,
+        })?;
+    Ok(input)
+}
+
+pub fn parse_puzzle(p: &str) -> Result<Id<'_>, Failure> {
+    Id::parse(p).ok_or_else(|| {
+        Failure::Usage(format!(
+            "`{p}` isn't a puzzle id; expected DAY-PART, like `1-1` (or `1b-1` for a variant, \
+             or `2024/1-1` for another year)."
+        ))
+    })
+}
+
+pub fn puzzle_fn(p: &str) -> Result<PartFn, Failure> {
+    let id = parse_puzzle(p)?;
+    solution::lookup(p).ok_or_else(|| {
+        let days: Vec<String> = solution::registry()
+            .filter(|q| q.year == id.year)
+            .map(|q| q.name())
+            .collect();
+        if days.is_empty() {
+            return Failure::Usage(format!("`{p}`: nothing's registered for {} yet.", id.year));
+        }
+        Failure::Usage(format!(
+            "`{p}` isn't a valid puzzle yet. Registered days are {}, each with parts 1 and 2.",
+            days.join(", ")
+        ))
+    })
+}
+
+/// Where the ledger keeps a puzzle's answer (see `Id::ledger_key`).
+pub fn ledger_key(p: &str) -> Result<String, Failure> {
+    Ok(parse_puzzle(p)?.ledger_key())
+}
+
+/// Call a puzzle part, turning panics into errors so one bad day can't take
+/// down a whole batch run. (The panic message still gets printed by the
+/// default hook as it happens.)
+pub fn run_part(id: &str, f: PartFn, input: &str, params: &Params) -> anyhow::Result<Answer> {
+    let res = std::panic::catch_unwind(AssertUnwindSafe(|| f(input, params)));
+    match res {
+        Ok(answer) => answer.with_context(|| format!("puzzle {id} returned an error")),
+        Err(payload) => {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "(no message)".to_string());
+            Err(anyhow!("puzzle {id} panicked: {msg}"))
+        }
+    }
+}
+
+/// A puzzle's params with the `--param` overrides applied.
+pub fn puzzle_params(p: &str, overrides: &[(String, String)]) -> Result<Params, Failure> {
+    parse_puzzle(p)?;
+    let puzzle = solution::find_id(p).expect("puzzle_fn already checked");
+    puzzle
+        .params(overrides.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+        .map_err(|e| Failure::Usage(format!("{e}.")))
+}
+
+/// Turns `all`, `3..7`, or `3..=7` into a list of registered days. Ranges use
+/// Rust semantics (so `3..7` stops at 6), and either end can be left off, like
+/// `8..`. A year in front, like `2024/all`, picks a past year's days instead.
+/// Returns None if the argument isn't a day selection at all.
+pub fn select_days(arg: &str) -> Option<Vec<&'static Puzzle>> {
+    let (year, arg) = match arg.split_once('/') {
+        Some((year, arg)) => (year.parse::<u32>().ok()?, arg),
+        None => (CURRENT_YEAR, arg),
+    };
+    let originals = solution::registry().filter(move |p| p.year == year && p.variant.is_empty());
+    if arg == "all" {
+        return Some(originals.collect());
+    }
+    let (start, end, inclusive) = if let Some((start, end)) = arg.split_once("..=") {
+        (start, end, true)
+    } else {
+        let (start, end) = arg.split_once("..")?;
+        (start, end, false)
+    };
+    let start = if start.is_empty() {
+        0
+    } else {
+        start.parse::<u32>().ok()?
+    };
+    let end = if end.is_empty() {
+        u32::MAX
+    } else if inclusive {
+        end.parse::<u32>().ok()?
+    } else {
+        end.parse::<u32>().ok()?.checked_sub(1)?
+    };
+
+    let days = originals
+        .filter(|p| (start..=end).contains(&p.day))
+        .collect();
+    Some(days)
+}
+
+/// How to run a batch of days.
+pub struct BatchOptions {
+    pub time: bool,
+    pub format: Format,
+    /// How many parts to run at once.
+    pub jobs: usize,
+    /// How long a part gets before we give up on it.
+    pub timeout: Option<Duration>,
+}
+
+/// Run both parts of every listed day, then print a summary. Failures get
+/// reported in the summary instead of stopping the whole run, since the point
+/// is to see everything at once; afterwards, we return the worst one.
+pub fn run_days(days: &[&Puzzle], ledger: &Ledger, options: &BatchOptions) -> Result<(), Failure> {
+    let mut reports = Vec::<Report>::with_capacity(days.len() * 2);
+    let mut worst: Option<Failure> = None;
+    let mut note = |failure: Failure| {
+        if worst
+            .as_ref()
+            .is_none_or(|w| failure.severity() > w.severity())
+        {
+            worst = Some(failure);
+        }
+    };
+
+    // Read all the inputs up front, and queue up a job for each part we can
+    // actually run. Missing inputs get reported right away.
+    let mut jobs = Vec::<Job>::with_capacity(days.len() * 2);
+    let mut job_reports = Vec::<usize>::with_capacity(days.len() * 2);
+    for puzzle in days {
+        let day = puzzle.day;
+        let input = match read_input(puzzle.year, day) {
+            Ok(input) => Some(Arc::new(input)),
+            Err(failure) => {
+                note(failure);
+                None
+            }
+        };
+        for part in [1, 2] {
+            if let Some(input) = &input {
+                jobs.push(Job {
+                    id: puzzle.id(part),
+                    f: puzzle.part(part).expect("parts 1 and 2 always exist"),
+                    input: input.clone(),
+                    params: puzzle.params([]).expect("no overrides to reject"),
+                });
+                job_reports.push(reports.len());
+            }
+            reports.push(Report {
+                year: puzzle.year,
+                day,
+                part,
+                title: puzzle.title,
+                outcome: Outcome::Missing(input_path(puzzle.year, day)),
+                duration: None,
+            });
+        }
+    }
+
+    let results = pool::run_jobs(&jobs, options.jobs, options.timeout);
+    for ((job, result), i) in jobs.iter().zip(results).zip(job_reports) {
+        let (outcome, elapsed) = match result {
+            JobResult::Done(Ok(output), elapsed) => {
+                let status = ledger.check(&job.id, &output);
+                if matches!(status, Status::Fail { .. }) {
+                    note(Failure::WrongAnswer);
+                }
+                (Outcome::Answered(output, Some(status)), elapsed)
+            }
+            JobResult::Done(Err(e), elapsed) => {
+                let outcome = Outcome::Error(format!("{e:#}"));
+                note(Failure::Crash(e));
+                (outcome, elapsed)
+            }
+            JobResult::TimedOut(elapsed) => {
+                note(Failure::Timeout {
+                    id: job.id.clone(),
+                    after: elapsed,
+                });
+                (Outcome::Timeout, elapsed)
+            }
+        };
+        reports[i].outcome = outcome;
+        reports[i].duration = Some(elapsed);
+    }
+
+    match options.format {
+        Format::Table => print_summary(&reports, options.time),
+        Format::Json => println!("{}", report::to_json(&reports)),
+    }
+    match worst {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
+}
+
+/// A single puzzle run, as a one-element JSON array so it looks like the
+/// batch version. A run that never started for want of an input is `Err`,
+/// and gets reported as missing, like in a batch.
+pub fn report_one(
+    id: &str,
+    result: Result<JobResult, Failure>,
+    ledger: Option<&Ledger>,
+) -> Result<(), Failure> {
+    let parsed = parse_puzzle(id)?;
+    let title = parsed.puzzle().expect("puzzle_fn already checked").title;
+    let key = parsed.ledger_key();
+    let (outcome, elapsed, result) = match result {
+        Err(failure) => {
+            let Failure::NoInput { path, .. } = &failure else {
+                return Err(failure);
+            };
+            (Outcome::Missing(path.clone()), None, Err(failure))
+        }
+        Ok(JobResult::Done(Ok(output), elapsed)) => {
+            let status = ledger.map(|l| l.check(&key, &output));
+            let result = match status {
+                Some(Status::Fail { .. }) => Err(Failure::WrongAnswer),
+                _ => Ok(()),
+            };
+            (Outcome::Answered(output, status), Some(elapsed), result)
+        }
+        Ok(JobResult::Done(Err(e), elapsed)) => (
+            Outcome::Error(format!("{e:#}")),
+            Some(elapsed),
+            Err(Failure::Crash(e)),
+        ),
+        Ok(JobResult::TimedOut(after)) => (
+            Outcome::Timeout,
+            Some(after),
+            Err(Failure::Timeout {
+                id: id.to_string(),
+                after,
+            }),
+        ),
+    };
+    let report = Report {
+        year: parsed.year,
+        day: parsed.day,
+        part: parsed.part,
+        title,
+        outcome,
+        duration: elapsed,
+    };
+    println!("{}", report::to_json(&[report]));
+    result
+}
+
+/// One row per day, with both parts side by side.
+fn print_summary(reports: &[Report], time: bool) {
+    let mut header = vec!["day", "title", "part 1", "part 2"];
+    if time {
+        header = vec!["day", "title", "part 1", "time", "part 2", "time"];
+    }
+    let mut rows = Vec::<Vec<String>>::new();
+    for report in reports {
+        if report.part == 1 {
+            rows.push(vec![report.day.to_string(), report.title.to_string()]);
+        }
+        let row = rows.last_mut().expect("part 1 comes first");
+        row.push(match &report.outcome {
+            Outcome::Answered(output, Some(status)) => checked_cell(status, output),
+            Outcome::Answered(output, None) => output.to_string(),
+            Outcome::Error(e) => format!("ERROR: {e}"),
+            Outcome::Timeout => "TIMEOUT".to_string(),
+            Outcome::Missing(path) => format!("(no {})", path.display()),
+        });
+        if time {
+            row.push(match report.duration {
+                Some(elapsed) => format!("{elapsed:.2?}"),
+                None => String::new(),
+            });
+        }
+    }
+    print_table(&header, &rows);
+}
+
+/// Like `PASS 1036` or `FAIL 1037 (expected 1036)`.
+fn checked_cell(status: &Status, output: &Answer) -> String {
+    // A grid won't fit in a table cell, so just squish it onto one line.
+    let output = output.to_string().replace('\n', "⏎");
+    match status {
+        Status::Fail { expected } => format!("{status} {output} (expected {expected})"),
+        _ => format!("{status} {output}"),
+    }
+}
+
+/// First column is right-aligned (it's the day number), the rest are
+/// left-aligned, and the last one doesn't get padded.
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let format_row = |cells: &mut dyn Iterator<Item = &str>| {
+        let mut line = String::new();
+        for (i, (cell, &width)) in cells.zip(&widths).enumerate() {
+            if i == 0 {
+                line.push_str(&format!("{cell:>width$}"));
+            } else if i == widths.len() - 1 {
+                line.push_str(&format!(" | {cell}"));
+            } else {
+                line.push_str(&format!(" | {cell:<width$}"));
+            }
+        }
+        line.trim_end().to_string()
+    };
+
+    println!();
+    println!("{}", format_row(&mut header.iter().copied()));
+    let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    println!("{}", rule.join("-+-"));
+    for row in rows {
+        println!("{}", format_row(&mut row.iter().map(String::as_str)));
+    }
+}
+
+/// Run one part against each of its examples, and compare with the answers
+/// the puzzle text gave.
+pub fn run_examples(id: &str, overrides: &[(String, String)], time: bool) -> Result<(), Failure> {
+    let f = puzzle_fn(id)?;
+    let part = parse_puzzle(id)?.part;
+    let puzzle = solution::find_id(id).expect("puzzle_fn already checked");
+    let fixtures = fixture::load(&fixture::fixtures_dir(), puzzle.year, puzzle.day)?;
+    let examples = fixture::examples(puzzle, &fixtures, part);
+    if examples.is_empty() {
+        return Err(Failure::Usage(format!("{id} doesn't have any examples.")));
+    }
+
+    let mut failed = false;
+    for (i, example) in examples.iter().enumerate() {
+        let (input, answer, example_params) = (example.input, example.answer, &example.params);
+        let params = puzzle
+            .params(
+                example_params
+                    .iter()
+                    .copied()
+                    .chain(overrides.iter().map(|(n, v)| (n.as_str(), v.as_str()))),
+            )
+            .map_err(|e| Failure::Usage(format!("{e}.")))?;
+        let (output, elapsed) = timed(|| run_part(id, f, input, &params));
+        let output = output?;
+        let status = if output == *answer {
+            "PASS"
+        } else {
+            failed = true;
+            "FAIL"
+        };
+        // Just the params that differ from the defaults, with the command
+        // line winning over the example.
+        let shown: Vec<String> = example_params
+            .iter()
+            .copied()
+            .filter(|(name, _)| !overrides.iter().any(|(n, _)| n == name))
+            .chain(overrides.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        if shown.is_empty() {
+            println!("example {}:", i + 1);
+        } else {
+            println!("example {} ({}):", i + 1, shown.join(", "));
+        }
+        println!("  expected: {answer}");
+        println!("  actual:   {output}");
+        println!("  {status}");
+        if time {
+            println!("  took {elapsed:.2?}");
+        }
+    }
+
+    if failed {
+        Err(Failure::WrongAnswer)
+    } else {
+        Ok(())
+    }
+}
+
+/// Wall-clock time for a closure. For a `PartFn`, that includes parsing.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Run one puzzle part a bunch of times and report the spread. Bails on the
+/// first error, since timing a failure isn't interesting.
+pub fn bench(
+    puzzle: &str,
+    runs: usize,
+    input_override: Option<&str>,
+    overrides: &[(String, String)],
+) -> Result<(), Failure> {
+    let f = puzzle_fn(puzzle)?;
+    let params = puzzle_params(puzzle, overrides)?;
+    let input = input_string(puzzle, input_override)?;
+    let mut durations = Vec::<Duration>::with_capacity(runs);
+    let mut output = Answer::Int(0);
+    for _ in 0..runs {
+        let (result, elapsed) = timed(|| run_part(puzzle, f, &input, &params));
+        output = result?;
+        durations.push(elapsed);
+    }
+    durations.sort();
+
+    let min = durations
+        .first()
+        .ok_or(Failure::Usage("`bench` needs at least one run".to_string()))?;
+    let median = durations[durations.len() / 2];
+    let max = durations[durations.len() - 1];
+    println!("output: {output}");
+    println!("{runs} runs: min {min:.2?}, median {median:.2?}, max {max:.2?}");
+    Ok(())
+}
+
+/// Durations like `30s`, `500ms`, or `2m`. A bare number means seconds.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (n, unit) = s.split_at(split);
+    let n: u64 = n.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(n)),
+        "" | "s" => Some(Duration::from_secs(n)),
+        // Too many minutes to count in seconds is as bad as a typo.
+        "m" => n.checked_mul(60).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+#[test]
+fn parse_duration_test() {
+    assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("30"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+    assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+    assert_eq!(parse_duration("2h"), None);
+    assert_eq!(parse_duration("s"), None);
+    assert_eq!(parse_duration("999999999999999999m"), None);
+    assert_eq!(
+        parse_duration("999999999999999999s"),
+        Some(Duration::from_secs(999999999999999999))
+    );
+}
@@ -0,0 +1,43 @@
+//! Throwaway directories for the tests that need to touch the filesystem.
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system temp dir, gone again once it's
+/// dropped (even if the test panics). The process id in the name keeps
+/// concurrent `cargo test` runs out of each other's way, and `name` does the
+/// same for tests within a run, so each test should pick its own.
+pub struct Scratch(PathBuf);
+
+impl Scratch {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("aoc25-{name}-{}", std::process::id()));
+        // Leftovers from a run that got killed before it could clean up.
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Deref for Scratch {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn scratch_test() {
+    let scratch = Scratch::new("scratch");
+    let path = scratch.to_path_buf();
+    std::fs::write(scratch.join("file"), "x").unwrap();
+    drop(scratch);
+    assert!(!path.exists());
+}
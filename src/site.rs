@@ -0,0 +1,330 @@
+//! `aoc25 fetch 12`: download a day's input, so nobody has to copy and paste
+//! it into `inputs/` anymore. And `aoc25 submit 12-1`, to send an answer back
+//! without a trip through the browser.
+//!
+//! The site wants your login's session cookie, which comes from
+//! `AOC25_SESSION`. `AOC25_URL` points us somewhere else, like a stand-in
+//! server for the tests. Inputs never change, so once we've got one we never
+//! ask again, and we space out our requests so as not to be a nuisance. The
+//! time of the last one lives in `.last-request` in the workspace root, so
+//! that holds across runs too.
+
+use crate::answer::Answer;
+use crate::guesses::Verdict;
+use crate::http;
+use crate::runner::input_file;
+use crate::util::log::{self, Level};
+use crate::util::write_atomic;
+use anyhow::{Context, bail};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// How long to wait between requests.
+pub const THROTTLE: Duration = Duration::from_secs(5);
+
+/// Where we get things from, and how.
+pub struct Site {
+    pub url: String,
+    pub session: Option<String>,
+    pub throttle: Duration,
+    /// Where the time of the last request gets written down.
+    pub stamp: PathBuf,
+}
+
+/// How `fetch` went.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// We already had it, so we didn't ask.
+    Cached(PathBuf),
+}
+
+impl Site {
+    /// The real site, unless the environment says otherwise.
+    pub fn from_env(root: &Path) -> Self {
+        Self {
+            url: std::env::var("AOC25_URL").unwrap_or_else(|_| DEFAULT_URL.to_string()),
+            session: std::env::var("AOC25_SESSION")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            throttle: THROTTLE,
+            stamp: root.join(".last-request"),
+        }
+    }
+
+    /// Downloads a day's input into `inputs/`, unless it's already there. An
+    /// empty file (like `make` leaves) doesn't count.
+    pub fn fetch(&self, root: &Path, year: u32, day: u32) -> anyhow::Result<Fetched> {
+        let path = root.join(input_file(year, day));
+        if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+        let url = format!("{}/{year}/day/{day}/input", self.url.trim_end_matches('/'));
+        let response = self.request(&url, None)?;
+        if response.status != 200 {
+            // The site's error pages are one short line of plain text.
+            bail!(
+                "{url} said {}: {}",
+                response.status,
+                response.body.lines().next().unwrap_or("(nothing)")
+            );
+        }
+        if response.body.is_empty() {
+            bail!("{url} sent back an empty input");
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("couldn't create {}", dir.display()))?;
+        }
+        write_atomic(&path, &response.body)
+            .with_context(|| format!("couldn't write {}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Sends an answer, and returns what the site made of it, plus what it
+    /// said in so many words.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &Answer,
+    ) -> anyhow::Result<(Verdict, String)> {
+        let url = format!("{}/{year}/day/{day}/answer", self.url.trim_end_matches('/'));
+        let form = format!(
+            "level={part}&answer={}",
+            http::form_encode(&answer.to_string())
+        );
+        let response = self.request(&url, Some(&form))?;
+        if response.status != 200 {
+            bail!(
+                "{url} said {}: {}",
+                response.status,
+                response.body.lines().next().unwrap_or("(nothing)")
+            );
+        }
+        let message = message(&response.body);
+        Ok((verdict(&message), message))
+    }
+
+    /// Sends a request as us, once it's been long enough since the last one.
+    fn request(&self, url: &str, form: Option<&str>) -> anyhow::Result<http::Response> {
+        let Some(session) = &self.session else {
+            bail!(
+                "AOC25_SESSION isn't set; copy the `session` cookie from your browser \
+                 after logging in to {}",
+                self.url
+            );
+        };
+        self.wait_turn()?;
+        let cookie = format!("session={session}");
+        let headers = [
+            ("Cookie", cookie.as_str()),
+            // The site asks tools to say who they are.
+            ("User-Agent", "aoc25 (a personal Advent of Code runner)"),
+        ];
+        http::request(url, &headers, form)
+    }
+
+    /// Sleeps off whatever's left of the throttle, then writes down that it's
+    /// our turn now.
+    fn wait_turn(&self) -> anyhow::Result<()> {
+        let last = std::fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            // The stamp lost its fraction of a millisecond, so make up for it
+            // rather than come in a hair early.
+            let wait =
+                (last + self.throttle + Duration::from_millis(1)).saturating_sub(since_epoch());
+            if !wait.is_zero() {
+                if log::enabled(Level::Info) {
+                    eprintln!("waiting {wait:.1?} to be polite");
+                }
+                std::thread::sleep(wait);
+            }
+        }
+        std::fs::write(&self.stamp, since_epoch().as_millis().to_string())
+            .with_context(|| format!("couldn't write {}", self.stamp.display()))
+    }
+}
+
+/// The site doesn't have an API for this, so we go by what the page says.
+fn verdict(message: &str) -> Verdict {
+    if message.contains("That's the right answer") {
+        Verdict::Right
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if message.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unclear
+    }
+}
+
+/// The page's `<article>` as plain text, minus the tags, the extra
+/// whitespace, and the link back to the puzzle.
+fn message(page: &str) -> String {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.split_once(" [Return to") {
+        Some((text, _)) => text.to_string(),
+        None => text,
+    }
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// A `Site` pointed at a test server, in a scratch workspace of its own.
+#[cfg(test)]
+pub fn test_site(name: &str, url: String) -> (Site, crate::scratch::Scratch) {
+    let root = crate::scratch::Scratch::new(&format!("site-{name}"));
+    std::fs::create_dir_all(root.join("inputs")).unwrap();
+    let site = Site {
+        url,
+        session: Some("cafe".to_string()),
+        throttle: Duration::ZERO,
+        stamp: root.join(".last-request"),
+    };
+    (site, root)
+}
+
+#[test]
+fn fetch_test() {
+    let (url, server) = http::serve(vec![(200, "1\n2\n3\n"), (200, "past\n")]);
+    let (site, root) = test_site("fetch", url);
+    // Like `make` leaves it.
+    std::fs::write(root.join("inputs/day12.txt"), "").unwrap();
+
+    let path = root.join("inputs/day12.txt");
+    assert_eq!(
+        site.fetch(&root, 2025, 12).unwrap(),
+        Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+    // The second time, the server doesn't hear about it.
+    assert_eq!(site.fetch(&root, 2025, 12).unwrap(), Fetched::Cached(path));
+    site.fetch(&root, 2024, 5).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(root.join("inputs/2024/day5.txt")).unwrap(),
+        "past\n"
+    );
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("GET /2025/day/12/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=cafe\r\n"));
+    assert!(requests[1].starts_with("GET /2024/day/5/input "));
+}
+
+#[test]
+fn fetch_errors_test() {
+    let (url, server) = http::serve(vec![(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks!",
+    )]);
+    let (mut site, root) = test_site("fetch-errors", url);
+    let err = site.fetch(&root, 2025, 12).unwrap_err().to_string();
+    assert!(err.contains("404: Please don't"), "{err}");
+    assert!(!root.join("inputs/day12.txt").exists());
+    server.join().unwrap();
+
+    site.session = None;
+    let err = site.fetch(&root, 2025, 12).unwrap_err().to_string();
+    assert!(err.contains("AOC25_SESSION"), "{err}");
+}
+
+#[test]
+fn throttle_test() {
+    let (url, server) = http::serve(vec![(200, "a"), (200, "b")]);
+    let (mut site, root) = test_site("throttle", url);
+    site.throttle = Duration::from_millis(300);
+    let start = std::time::Instant::now();
+    site.fetch(&root, 2025, 1).unwrap();
+    site.fetch(&root, 2025, 2).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    server.join().unwrap();
+}
+
+#[test]
+fn submit_test() {
+    let (url, server) = http::serve(vec![
+        (
+            200,
+            "<main>\n<article><p>That's not the right answer; your answer is too high. \
+             Please wait one minute before trying again. \
+             <a href=\"/2025/day/12\">[Return to Day 12]</a></p></article>\n</main>",
+        ),
+        (
+            200,
+            "<article><p>You gave an answer too recently; you have to wait after \
+             submitting an answer before trying again. You have 41s left to wait.</p></article>",
+        ),
+        (
+            200,
+            "<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>",
+        ),
+    ]);
+    let (site, _root) = test_site("submit", url);
+    let (verdict, message) = site.submit(2025, 12, 1, &Answer::Int(-5)).unwrap();
+    assert_eq!(verdict, Verdict::TooHigh);
+    assert_eq!(
+        message,
+        "That's not the right answer; your answer is too high. \
+         Please wait one minute before trying again."
+    );
+    let (verdict, message) = site.submit(2025, 12, 1, &Answer::Int(4)).unwrap();
+    assert_eq!(verdict, Verdict::RateLimited);
+    assert!(message.ends_with("You have 41s left to wait."));
+    let (verdict, _) = site
+        .submit(2024, 3, 2, &Answer::Text("A B".into()))
+        .unwrap();
+    assert_eq!(verdict, Verdict::Right);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /2025/day/12/answer HTTP/1.1\r\n"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=-5"));
+    assert!(requests[2].starts_with("POST /2024/day/3/answer "));
+    assert!(requests[2].ends_with("level=2&answer=A+B"));
+}
+
+#[test]
+fn verdict_test() {
+    let wrong =
+        "That's not the right answer. If you're stuck, make sure you're using the full input data";
+    assert_eq!(verdict(wrong), Verdict::Wrong);
+    let low = "That's not the right answer; your answer is too low.";
+    assert_eq!(verdict(low), Verdict::TooLow);
+    let solved = "You don't seem to be solving the right level. Did you already complete it?";
+    assert_eq!(verdict(solved), Verdict::WrongLevel);
+    assert_eq!(verdict("500 Internal Server Error"), Verdict::Unclear);
+}
@@ -0,0 +1,451 @@
+//! The standard interface for a day's puzzle, plus the registry that the runner
+//! looks things up in.
+//!
+//! Each day module implements `Solution` for a unit struct and then registers
+//! itself by exporting a `PUZZLE` const built with `Puzzle::of`. The `days!`
+//! macro (invoked in dispatch.rs) declares the modules and collects those
+//! consts into `REGISTRY`, so adding a day is one line there plus whatever the
+//! module itself says.
+//!
+//! Past years work the same way, one level down: `years!` declares a module
+//! per year (like `dispatch::y2024`), each with a `days!` of its own.
+
+use crate::answer::Answer;
+use anyhow::{anyhow, bail};
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// The season this crate is named for. Puzzle ids without a year mean this one.
+pub const CURRENT_YEAR: u32 = 2025;
+
+/// A day's puzzle. Parsing is split out from the parts so both parts can share
+/// it, and `Input` can borrow from the input text if that's convenient.
+pub trait Solution {
+    const YEAR: u32 = CURRENT_YEAR;
+    const DAY: u32;
+    /// Empty for the original, or a letter like `b` for an alternate take on
+    /// the same day that lives next to it in `dayNb.rs`.
+    const VARIANT: &'static str = "";
+    const TITLE: &'static str;
+    /// The examples from the puzzle text, with their expected answers.
+    const EXAMPLES: &'static [Example];
+    /// Knobs the parts read out of their `Params`. Most days don't have any.
+    const PARAMS: &'static [Param] = &[];
+
+    type Input<'a>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>, params: &Params) -> anyhow::Result<Answer>;
+    fn part2(input: &Self::Input<'_>, params: &Params) -> anyhow::Result<Answer>;
+
+    /// Parse, then solve.
+    fn run_part1(input: &str, params: &Params) -> anyhow::Result<Answer> {
+        Self::part1(&Self::parse(input)?, params)
+    }
+
+    fn run_part2(input: &str, params: &Params) -> anyhow::Result<Answer> {
+        Self::part2(&Self::parse(input)?, params)
+    }
+}
+
+/// Takes the text of a puzzle input and returns the answer.
+pub type PartFn = fn(&str, &Params) -> anyhow::Result<Answer>;
+
+/// A named knob for a puzzle, for when the examples are smaller than the real
+/// thing in some way that the puzzle text spells out (like day 8 making 10
+/// connections instead of 1000). The default is what the real input wants.
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+    /// For a number, the values that make sense. Overrides outside it get
+    /// turned away before the puzzle ever sees them.
+    pub range: Option<RangeInclusive<i64>>,
+}
+
+impl Param {
+    /// What `range` allows, in words, like "a number from 1 up".
+    pub fn sensible(&self) -> Option<String> {
+        let range = self.range.as_ref()?;
+        Some(match *range.end() {
+            i64::MAX => format!("a number from {} up", range.start()),
+            end => format!("a number from {} to {end}", range.start()),
+        })
+    }
+}
+
+/// The values of a puzzle's params for one run: the declared defaults, plus
+/// whatever an example or the command line overrode.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.values.retain(|(n, _)| n != name);
+        self.values.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Errors if the param wasn't set (which means the puzzle forgot to
+    /// declare it), or if it was set to something that doesn't parse.
+    pub fn get<T: FromStr>(&self, name: &str) -> anyhow::Result<T>
+    where
+        T::Err: Display,
+    {
+        let (_, value) = self
+            .values
+            .iter()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| anyhow!("param `{name}` isn't set; is it in PARAMS?"))?;
+        value
+            .parse()
+            .map_err(|e| anyhow!("param `{name}` = `{value}`: {e}"))
+    }
+}
+
+/// One of the worked examples from the puzzle text.
+pub struct Example {
+    pub part: u8,
+    pub input: &'static str,
+    pub answer: Answer,
+    /// Param overrides, for anything the example does differently from the
+    /// real input.
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    pub const fn new(part: u8, input: &'static str, answer: Answer) -> Self {
+        Self {
+            part,
+            input,
+            answer,
+            params: &[],
+        }
+    }
+}
+
+/// For day modules' tests: run every example for one part and compare,
+/// fixtures included.
+#[cfg(test)]
+pub fn assert_examples<S: Solution>(part: u8) {
+    use crate::fixture;
+
+    let puzzle = Puzzle::of::<S>();
+    let f = puzzle.part(part).expect("only parts 1 and 2");
+    let fixtures = fixture::load(&fixture::fixtures_dir(), puzzle.year, puzzle.day).unwrap();
+    let examples = fixture::examples(&puzzle, &fixtures, part);
+    assert!(!examples.is_empty(), "no examples for part {part}");
+    for example in examples {
+        let params = puzzle
+            .params(example.params.iter().copied())
+            .expect("known params");
+        let answer = f(example.input, &params).expect("should ok");
+        assert_eq!(answer, *example.answer);
+    }
+}
+
+/// A type-erased `Solution`, so days with different `Input` types can live in
+/// the same list.
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub variant: &'static str,
+    pub title: &'static str,
+    pub examples: &'static [Example],
+    pub params: &'static [Param],
+    pub part1: PartFn,
+    pub part2: PartFn,
+}
+
+impl Puzzle {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            variant: S::VARIANT,
+            title: S::TITLE,
+            examples: S::EXAMPLES,
+            params: S::PARAMS,
+            part1: S::run_part1,
+            part2: S::run_part2,
+        }
+    }
+
+    /// The declared defaults with some overrides on top. Overriding a param
+    /// the puzzle doesn't declare is an error, since it'd just get ignored.
+    pub fn params<'a>(
+        &self,
+        overrides: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> anyhow::Result<Params> {
+        let mut params = Params::default();
+        for param in self.params {
+            params = params.with(param.name, param.default);
+        }
+        for (name, value) in overrides {
+            let Some(param) = self.params.iter().find(|p| p.name == name) else {
+                let known: Vec<&str> = self.params.iter().map(|p| p.name).collect();
+                return Err(match known.is_empty() {
+                    true => anyhow!("day {} doesn't take any params", self.name()),
+                    false => anyhow!(
+                        "day {} doesn't have a param `{name}`; it has {}",
+                        self.name(),
+                        known.join(", ")
+                    ),
+                });
+            };
+            if let (Some(range), Some(sensible)) = (&param.range, param.sensible())
+                && !value.parse().is_ok_and(|n| range.contains(&n))
+            {
+                bail!("param `{name}` = `{value}`, but it should be {sensible}");
+            }
+            params = params.with(name, value);
+        }
+        Ok(params)
+    }
+
+    /// Like `11b`, or `2024/5` for a past year.
+    pub fn name(&self) -> String {
+        format!("{}{}{}", year_prefix(self.year), self.day, self.variant)
+    }
+
+    /// The id of one of its parts, like `2024/5-2`.
+    pub fn id(&self, part: u8) -> String {
+        format!("{}-{part}", self.name())
+    }
+
+    /// The module it lives in, relative to `src/dispatch/`: like `day11b`, or
+    /// `y2024/day5` for a past year.
+    pub fn module(&self) -> String {
+        match self.year {
+            CURRENT_YEAR => format!("day{}{}", self.day, self.variant),
+            year => format!("y{year}/day{}{}", self.day, self.variant),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<PartFn> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+/// Declares the day modules and builds `REGISTRY` out of their `PUZZLE`s.
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        $(pub mod $day;)*
+
+        /// Every registered puzzle, in day order.
+        pub const REGISTRY: &[$crate::solution::Puzzle] = &[$($day::PUZZLE),*];
+    };
+}
+pub(crate) use days;
+
+/// Declares the past years' modules, each one a `days!` of its own, and
+/// collects their registries into `PAST_YEARS`.
+macro_rules! years {
+    ($($year:ident),* $(,)?) => {
+        $(pub mod $year;)*
+
+        /// Every past year's `REGISTRY`, oldest first.
+        pub const PAST_YEARS: &[&[$crate::solution::Puzzle]] = &[$($year::REGISTRY),*];
+    };
+}
+pub(crate) use years;
+
+/// Every registered puzzle: this year's, then the past years'.
+pub fn registry() -> impl Iterator<Item = &'static Puzzle> {
+    let past = crate::dispatch::PAST_YEARS.iter().copied().flatten();
+    crate::dispatch::REGISTRY.iter().chain(past)
+}
+
+/// The original version of one of this year's days, not any of its variants.
+pub fn find(day: u32) -> Option<&'static Puzzle> {
+    find_in(CURRENT_YEAR, day, "")
+}
+
+pub fn find_in(year: u32, day: u32, variant: &str) -> Option<&'static Puzzle> {
+    registry().find(|p| p.year == year && p.day == day && p.variant == variant)
+}
+
+/// `2024/` for a past year, and nothing for this one.
+pub fn year_prefix(year: u32) -> String {
+    match year {
+        CURRENT_YEAR => String::new(),
+        year => format!("{year}/"),
+    }
+}
+
+/// Splits a day like `2024/11b` into a year, day number, and variant (which
+/// might be empty). No year means this one. Variants are lowercase letters.
+pub fn parse_day(day: &str) -> Option<(u32, u32, &str)> {
+    let (year, day) = match day.split_once('/') {
+        Some((year, day)) => (year.parse().ok()?, day),
+        None => (CURRENT_YEAR, day),
+    };
+    let split = day.find(|c: char| !c.is_ascii_digit()).unwrap_or(day.len());
+    let (day, variant) = day.split_at(split);
+    if !variant.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    Some((year, day.parse().ok()?, variant))
+}
+
+/// A puzzle id like `1-2`, picked apart. It can start with a year, like
+/// `2024/5-2`, and name a variant, like `11b-2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Id<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub variant: &'a str,
+    pub part: u8,
+}
+
+impl<'a> Id<'a> {
+    pub fn parse(id: &'a str) -> Option<Self> {
+        let (day, part) = id.split_once('-')?;
+        let (year, day, variant) = parse_day(day)?;
+        Some(Self {
+            year,
+            day,
+            variant,
+            part: part.parse().ok()?,
+        })
+    }
+
+    /// Where the ledger keeps this one's answer. Variants are solving the
+    /// same puzzle, so `11b-2` gets checked against `11-2`, and this year's
+    /// ids go without a year, like they always have.
+    pub fn ledger_key(&self) -> String {
+        format!("{}{}-{}", year_prefix(self.year), self.day, self.part)
+    }
+
+    pub fn puzzle(&self) -> Option<&'static Puzzle> {
+        find_in(self.year, self.day, self.variant)
+    }
+}
+
+/// The puzzle an id like `1-2`, `11b-2`, or `2024/5-2` refers to.
+pub fn find_id(id: &str) -> Option<&'static Puzzle> {
+    Id::parse(id)?.puzzle()
+}
+
+/// Maps puzzle identifiers like `1-2` to implementation functions.
+pub fn lookup(id: &str) -> Option<PartFn> {
+    let id = Id::parse(id)?;
+    id.puzzle()?.part(id.part)
+}
+
+#[test]
+fn params_test() {
+    let params = Params::default().with("connect", "10").with("name", "lol");
+    assert_eq!(params.get::<usize>("connect").unwrap(), 10);
+    assert!(params.get::<String>("missing").is_err());
+    assert!(params.get::<usize>("name").is_err());
+    let params = params.with("connect", "1000");
+    assert_eq!(params.get::<usize>("connect").unwrap(), 1000);
+}
+
+#[test]
+fn declared_params_test() {
+    let day8 = find(8).unwrap();
+    let defaults = day8.params([]).unwrap();
+    assert_eq!(defaults.get::<usize>("connect").unwrap(), 1000);
+    let overridden = day8.params([("connect", "10")]).unwrap();
+    assert_eq!(overridden.get::<usize>("connect").unwrap(), 10);
+    assert!(day8.params([("conect", "10")]).is_err());
+    let err = day8.params([("connect", "0")]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "param `connect` = `0`, but it should be a number from 1 up"
+    );
+    assert!(day8.params([("connect", "lots")]).is_err());
+    assert!(find(1).unwrap().params([("connect", "10")]).is_err());
+}
+
+#[test]
+fn examples_use_declared_params_test() {
+    for puzzle in registry() {
+        for example in puzzle.examples {
+            assert!(
+                puzzle.params(example.params.iter().copied()).is_ok(),
+                "day {} has an example with an undeclared param",
+                puzzle.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn every_part_has_an_example_test() {
+    use crate::fixture;
+
+    for puzzle in registry() {
+        let fixtures = fixture::load(&fixture::fixtures_dir(), puzzle.year, puzzle.day).unwrap();
+        for part in [1, 2] {
+            assert!(
+                !fixture::examples(puzzle, &fixtures, part).is_empty(),
+                "day {} part {part} has no example",
+                puzzle.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn lookup_test() {
+    assert!(lookup("1-1").is_some());
+    assert!(lookup("1-2").is_some());
+    assert!(lookup("1-3").is_none());
+    assert!(lookup("99-1").is_none());
+    assert!(lookup("1").is_none());
+    assert!(lookup("lol-1").is_none());
+    assert!(lookup("1z-1").is_none(), "no such variant");
+    assert!(lookup("1B-1").is_none());
+    assert!(lookup("2025/1-1").is_some(), "this year, spelled out");
+    assert!(lookup("1999/1-1").is_none());
+}
+
+#[test]
+fn parse_id_test() {
+    let id = |year, day, variant, part| Id {
+        year,
+        day,
+        variant,
+        part,
+    };
+    assert_eq!(Id::parse("11-2"), Some(id(2025, 11, "", 2)));
+    assert_eq!(Id::parse("11b-2"), Some(id(2025, 11, "b", 2)));
+    assert_eq!(Id::parse("2024/5-1"), Some(id(2024, 5, "", 1)));
+    assert_eq!(Id::parse("2024/-1"), None);
+    assert_eq!(Id::parse("11b-2").unwrap().ledger_key(), "11-2");
+    assert_eq!(Id::parse("2024/5b-1").unwrap().ledger_key(), "2024/5-1");
+    assert_eq!(parse_day("11"), Some((2025, 11, "")));
+    assert_eq!(parse_day("b"), None);
+    assert_eq!(parse_day("11-b"), None);
+    assert_eq!(parse_day("twenty/11"), None);
+}
+
+#[test]
+fn registry_order_test() {
+    let registries = std::iter::once(crate::dispatch::REGISTRY)
+        .chain(crate::dispatch::PAST_YEARS.iter().copied());
+    for registry in registries {
+        let days: Vec<(u32, u32, &str)> = registry
+            .iter()
+            .map(|p| (p.year, p.day, p.variant))
+            .collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+        assert!(
+            days.iter().all(|d| d.0 == days[0].0),
+            "one year per registry"
+        );
+    }
+}
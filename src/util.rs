@@ -1,7 +1,14 @@
 use anyhow::anyhow;
 use std::ops::RangeInclusive;
+use std::path::Path;
 
+pub mod cancel;
+mod combinate;
+pub mod log;
+mod parse;
 mod vec;
+pub use combinate::*;
+pub use parse::*;
 pub use vec::*;
 
 /// Turns a string like "5-8" into a RangeInclusive.
@@ -12,9 +19,44 @@ pub fn parse_range(txt: &str) -> Result<RangeInclusive<u64>, anyhow::Error> {
     Ok(start..=end)
 }
 
+/// `parse_range` for a piece of a line, with errors that say where.
+pub fn parse_line_range(line: Line, txt: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    let Some((first, second)) = txt.split_once('-') else {
+        return Err(line.error(txt, "expected a range like `5-8`"));
+    };
+    let number = |n: &str| {
+        let n = n.trim();
+        n.parse::<u64>()
+            .map_err(|e| line.error(n, format!("expected a number ({e})")))
+    };
+
+    Ok(number(first)?..=number(second)?)
+}
+
+/// Like `fs::read_to_string`, except a file that isn't there is `None`
+/// instead of an error, for files that only turn up once there's something in
+/// them.
+pub fn read_if_exists(path: &Path) -> std::io::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Like `fs::write`, but into a temp file next door that then gets renamed
+/// over `path`, so a crash can't leave half a file that looks like a whole one.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
+
 /// A two-dimensional grid, implemented as a single-dimensional array that
 /// translates coordinates <-> indices on the fly. Zero-indexed coordinates,
 /// positive Y is down (like raster graphics).
+#[derive(Clone)]
 pub struct Grid<T> {
     pub storage: Vec<T>,
     pub width: usize,
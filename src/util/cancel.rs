@@ -0,0 +1,68 @@
+//! Cooperative cancellation, for solutions that might run approximately
+//! forever. When the runner gives up on a part (`--timeout`), it cancels that
+//! part's token; long loops can call `check()` and bail out, instead of
+//! burning a core until the process exits.
+//!
+//! The token rides along in a thread-local, so nothing has to thread it
+//! through the `Solution` signatures. Outside a runner (like in tests), there's
+//! no token and `check()` always says keep going.
+
+use anyhow::anyhow;
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` with this as the current thread's token, so `check()` in
+    /// there can see it.
+    pub fn install<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|c| c.replace(Some(self.clone())));
+        let res = f();
+        CURRENT.with(|c| c.replace(previous));
+        res
+    }
+}
+
+/// Errors if the runner has given up on us. Cheap enough for a hot loop.
+pub fn check() -> anyhow::Result<()> {
+    let cancelled = CURRENT.with(|c| c.borrow().as_ref().is_some_and(|t| t.is_cancelled()));
+    if cancelled {
+        Err(anyhow!("cancelled"))
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn cancel_test() {
+    assert!(check().is_ok());
+    let token = CancelToken::new();
+    token.install(|| {
+        assert!(check().is_ok());
+        token.cancel();
+        assert!(check().is_err());
+    });
+    // Only inside install().
+    assert!(check().is_ok());
+}
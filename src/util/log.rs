@@ -0,0 +1,61 @@
+//! Opt-in diagnostic output for the day modules, so their traces don't swamp
+//! the answer or slow down a bench run. It all goes to stderr, which keeps
+//! stdout clean for `--format json`.
+//!
+//! The runner sets the level from `-q`/`-v`/`-vv`; days just call `debug!` or
+//! `trace!` like they'd call `println!`. The format args only get evaluated if
+//! the level is on, so it's fine to leave them in hot loops.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Nothing but the answer.
+    Quiet,
+    /// The default: whatever the runner normally says.
+    Info,
+    /// A line or two per step of the solution.
+    Debug,
+    /// Everything. Expect a firehose.
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Like `eprintln!`, but only with `-v`.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::util::log::enabled($crate::util::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Like `eprintln!`, but only with `-vv`.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::util::log::enabled($crate::util::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use {debug, trace};
+
+#[test]
+fn levels_test() {
+    assert!(Level::Quiet < Level::Info);
+    assert!(Level::Debug < Level::Trace);
+    // The level is global, so only check the default here rather than flipping
+    // it out from under the other tests.
+    assert!(enabled(Level::Info));
+    assert!(!enabled(Level::Debug));
+}
@@ -0,0 +1,158 @@
+//! Parse errors that say where the problem is, so a bad or truncated input
+//! gets you "line 412, column 7: expected a number" instead of a wrong answer
+//! or a shrug.
+//!
+//! Parsers walk `numbered_lines` and, when something's off, point at the bad
+//! piece with `Line::error`. The piece has to be sliced out of the line (which
+//! it always is, if you got it from `split` and friends), since that's how we
+//! work out the column.
+
+use std::fmt::Display;
+
+/// How much of the offending text to quote. Past this, it's just noise.
+const SNIPPET_CHARS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Counting from 1, like editors do.
+    pub line: usize,
+    /// In chars, also from 1.
+    pub column: usize,
+    /// The bad piece, or empty if what's wrong is that something's missing.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if self.snippet.is_empty() {
+            write!(f, ", but the line ends there")
+        } else {
+            write!(f, ", at {:?}", self.snippet)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One line of an input, and where it was.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl Line<'_> {
+    /// An error pointing at `at`, which should be a piece of this line. Pass
+    /// an empty piece from the end (like `&text[text.len()..]`) to complain
+    /// that the line stopped short.
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= self.text.len() && self.text.is_char_boundary(offset))
+            // Not from this line after all, so the best we can do is the start.
+            .unwrap_or(0);
+        ParseError {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            snippet: at.chars().take(SNIPPET_CHARS).collect(),
+            message: message.into(),
+        }
+    }
+
+    /// The empty piece at the end of the line, for `error`.
+    pub fn end(&self) -> &str {
+        &self.text[self.text.len()..]
+    }
+
+    /// The first char that `ok` turns its nose up at, as a piece for `error`.
+    pub fn first_bad_char(&self, ok: impl Fn(char) -> bool) -> Option<&str> {
+        self.text
+            .char_indices()
+            .find(|&(_, c)| !ok(c))
+            .map(|(i, c)| &self.text[i..i + c.len_utf8()])
+    }
+
+    /// Exactly `N` numbers separated by commas, like `162,817,812`.
+    pub fn comma_numbers<const N: usize>(&self) -> Result<[i64; N], ParseError> {
+        let mut numbers = [0; N];
+        let mut pieces = self.text.split(',');
+        for number in numbers.iter_mut() {
+            let Some(piece) = pieces.next() else {
+                return Err(self.error(
+                    self.end(),
+                    format!("expected {N} numbers separated by commas"),
+                ));
+            };
+            let piece = piece.trim();
+            *number = piece
+                .parse()
+                .map_err(|e| self.error(piece, format!("expected a number ({e})")))?;
+        }
+        if let Some(extra) = pieces.next() {
+            return Err(self.error(extra, format!("expected only {N} numbers")));
+        }
+        Ok(numbers)
+    }
+}
+
+/// Like `str::lines`, but each one knows its line number.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+#[test]
+fn parse_error_test() {
+    let input = "L68\nR1x4\n";
+    let line = numbered_lines(input).nth(1).unwrap();
+    let err = line.error(&line.text[1..], "expected a number");
+    assert_eq!((err.line, err.column), (2, 2));
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 2: expected a number, at \"1x4\""
+    );
+
+    let err = line.error(line.end(), "expected more");
+    assert_eq!(err.column, 5);
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 5: expected more, but the line ends there"
+    );
+
+    // Columns count chars, not bytes.
+    let line = Line {
+        number: 1,
+        text: "é?",
+    };
+    assert_eq!(line.error(&line.text[2..], "huh").column, 2);
+    // A piece from somewhere else gets pinned to the start.
+    assert_eq!(line.error("elsewhere", "huh").column, 1);
+
+    let bad = line.first_bad_char(|c| c.is_ascii()).unwrap();
+    assert_eq!((bad, line.error(bad, "huh").column), ("é", 1));
+    assert_eq!(line.first_bad_char(|_| true), None);
+}
+
+#[test]
+fn comma_numbers_test() {
+    let line = |text| Line { number: 3, text };
+    assert_eq!(line("162,-817, 812").comma_numbers(), Ok([162, -817, 812]));
+    assert_eq!(
+        line("7,1").comma_numbers::<3>().unwrap_err().to_string(),
+        "line 3, column 4: expected 3 numbers separated by commas, but the line ends there"
+    );
+    assert_eq!(
+        line("7,x").comma_numbers::<2>().unwrap_err().to_string(),
+        "line 3, column 3: expected a number (invalid digit found in string), at \"x\""
+    );
+    assert_eq!(line("7,1,4").comma_numbers::<2>().unwrap_err().column, 5);
+}
@@ -1,6 +1,8 @@
+use super::{Line, ParseError};
 use std::fmt::Display;
 use std::num::ParseIntError;
 use std::ops::{Add, Sub};
+use std::str::FromStr;
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Default)]
 pub struct Vec3 {
@@ -9,18 +11,30 @@ pub struct Vec3 {
     pub z: i64,
 }
 
-impl Vec3 {
-    pub fn new(x: i64, y: i64, z: i64) -> Self {
-        Self { x, y, z }
-    }
+/// Like "162,817,812".
+impl FromStr for Vec3 {
+    type Err = ParseIntError;
 
-    pub fn from_str(line: &str) -> Result<Vec3, ParseIntError> {
+    fn from_str(line: &str) -> Result<Vec3, ParseIntError> {
         let mut stuff = line.split(',');
         let x = stuff.next().unwrap_or_default().parse::<i64>()?;
         let y = stuff.next().unwrap_or_default().parse::<i64>()?;
         let z = stuff.next().unwrap_or_default().parse::<i64>()?;
         Ok(Vec3::new(x, y, z))
     }
+}
+
+impl Vec3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Like `from_str`, but errors say where, and a fourth number is an error
+    /// instead of getting quietly dropped.
+    pub fn from_line(line: Line) -> Result<Self, ParseError> {
+        let [x, y, z] = line.comma_numbers()?;
+        Ok(Self::new(x, y, z))
+    }
 
     fn square_components(&self) -> Self {
         Self {
@@ -83,15 +97,26 @@ pub struct Vec2 {
     pub y: i64,
 }
 
+/// Like "7,1".
+impl FromStr for Vec2 {
+    type Err = ParseIntError;
+
+    fn from_str(line: &str) -> Result<Self, ParseIntError> {
+        let mut stuff = line.split(',');
+        let x = stuff.next().unwrap_or_default().parse::<i64>()?;
+        let y = stuff.next().unwrap_or_default().parse::<i64>()?;
+        Ok(Self::new(x, y))
+    }
+}
+
 impl Vec2 {
     pub fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
 
-    pub fn from_str(line: &str) -> Result<Self, ParseIntError> {
-        let mut stuff = line.split(',');
-        let x = stuff.next().unwrap_or_default().parse::<i64>()?;
-        let y = stuff.next().unwrap_or_default().parse::<i64>()?;
+    /// Same deal as `Vec3::from_line`.
+    pub fn from_line(line: Line) -> Result<Self, ParseError> {
+        let [x, y] = line.comma_numbers()?;
         Ok(Self::new(x, y))
     }
 
@@ -0,0 +1,160 @@
+//! `aoc25 watch 10-2`: keep re-running a puzzle part while you work on it.
+//!
+//! We can't just call the part fn, since the whole point is that its source
+//! is changing under us; instead we poll the day's source and input files, and
+//! when either one moves, shell out to `cargo run` for the part and for its
+//! examples. Then we print how the answer changed since the last run.
+
+use crate::failure::Failure;
+use crate::runner::input_file;
+use crate::solution::Puzzle;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs until you kill it. `crate_dir` is where the source and Cargo.toml
+/// are, and `root` is where the inputs and ledger are; usually they're the
+/// same place.
+pub fn watch(id: &str, puzzle: &Puzzle, crate_dir: &Path, root: &Path) -> Result<(), Failure> {
+    let watched = [
+        crate_dir.join(format!("src/dispatch/{}.rs", puzzle.module())),
+        root.join(input_file(puzzle.year, puzzle.day)),
+    ];
+    let mut stamps: Vec<Option<SystemTime>> = vec![None; watched.len()];
+    let mut previous: Option<String> = None;
+    let mut first = true;
+    println!("watching {} (ctrl-c to stop)", describe(&watched));
+
+    loop {
+        let current: Vec<Option<SystemTime>> = watched.iter().map(|p| mtime(p)).collect();
+        let changed: Vec<&PathBuf> = watched
+            .iter()
+            .zip(current.iter().zip(&stamps))
+            .filter(|(_, (now, before))| now != before)
+            .map(|(path, _)| path)
+            .collect();
+        if first || !changed.is_empty() {
+            if first {
+                println!("\n--- {id} ---");
+            } else {
+                println!("\n--- {id}: {} changed ---", describe(&changed));
+            }
+            first = false;
+            stamps = current;
+            run_once(id, crate_dir, root, &mut previous)?;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// One round of `cargo run`s. Updates `previous` if we got an answer.
+fn run_once(
+    id: &str,
+    crate_dir: &Path,
+    root: &Path,
+    previous: &mut Option<String>,
+) -> Result<(), Failure> {
+    let real = cargo_run(crate_dir, root, &[id, "-q"])?;
+    match real.status.code() {
+        // -q means stdout is just the answer.
+        Some(0) | Some(4) => {
+            let answer = String::from_utf8_lossy(&real.stdout).trim_end().to_string();
+            println!("{}", answer_diff(previous.as_deref(), &answer));
+            if real.status.code() == Some(4) {
+                println!("ledger: FAIL");
+            }
+            *previous = Some(answer);
+        }
+        _ => {
+            // A compile error or a crash; either way, stderr has the story.
+            println!("{}", tail(&String::from_utf8_lossy(&real.stderr), 20));
+        }
+    }
+
+    let examples = cargo_run(crate_dir, root, &[id, "--example", "-q"])?;
+    let verdict = match examples.status.code() {
+        Some(0) => "PASS",
+        Some(4) => "FAIL",
+        _ => "ERROR",
+    };
+    println!("examples: {verdict}");
+    Ok(())
+}
+
+fn cargo_run(crate_dir: &Path, root: &Path, args: &[&str]) -> Result<Output, Failure> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .arg("run")
+        .arg("-q")
+        .arg("--manifest-path")
+        .arg(crate_dir.join("Cargo.toml"))
+        .arg("--")
+        .args(args)
+        // So the child finds the same inputs and ledger we would.
+        .env("AOC25_ROOT", root)
+        .output()
+        .map_err(|e| Failure::Crash(anyhow::anyhow!("couldn't run cargo: {e}")))?;
+    Ok(output)
+}
+
+/// None if the file doesn't exist (yet), so creating it counts as a change.
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn describe(paths: &[impl AsRef<Path>]) -> String {
+    let names: Vec<String> = paths
+        .iter()
+        .map(|p| p.as_ref().display().to_string())
+        .collect();
+    names.join(" and ")
+}
+
+fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+/// How the answer moved since last time. One line for a number; for a grid,
+/// just the rows that changed.
+fn answer_diff(previous: Option<&str>, answer: &str) -> String {
+    let Some(previous) = previous else {
+        return format!("answer: {answer}");
+    };
+    if previous == answer {
+        return format!("answer: {answer} (unchanged)");
+    }
+    if !previous.contains('\n') && !answer.contains('\n') {
+        return format!("answer: {answer} (was {previous})");
+    }
+    let old: Vec<&str> = previous.lines().collect();
+    let new: Vec<&str> = answer.lines().collect();
+    let mut out = String::from("answer changed:");
+    for row in 0..old.len().max(new.len()) {
+        let (before, after) = (old.get(row), new.get(row));
+        if before == after {
+            continue;
+        }
+        out.push_str(&format!("\n  row {row}:"));
+        if let Some(before) = before {
+            out.push_str(&format!("\n    - {before}"));
+        }
+        if let Some(after) = after {
+            out.push_str(&format!("\n    + {after}"));
+        }
+    }
+    out
+}
+
+#[test]
+fn answer_diff_test() {
+    assert_eq!(answer_diff(None, "33"), "answer: 33");
+    assert_eq!(answer_diff(Some("33"), "33"), "answer: 33 (unchanged)");
+    assert_eq!(answer_diff(Some("32"), "33"), "answer: 33 (was 32)");
+    assert_eq!(
+        answer_diff(Some("#.\n.#\n##"), "#.\n##"),
+        "answer changed:\n  row 1:\n    - .#\n    + ##\n  row 2:\n    - ##"
+    );
+}
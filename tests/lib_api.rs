@@ -0,0 +1,29 @@
+//! Checks that the library half of the crate is usable from outside it.
+
+use aoc25::solution::{Params, lookup};
+use aoc25::util::{CombinateIndicesUnrepeated, Vec3, parse_range};
+
+#[test]
+fn util_is_reusable_test() {
+    assert_eq!(parse_range("5-8").unwrap(), 5..=8);
+    assert_eq!("1,2,3".parse::<Vec3>().unwrap(), Vec3::new(1, 2, 3));
+    let pairs = CombinateIndicesUnrepeated::try_new(0..4, 2)
+        .unwrap()
+        .count();
+    assert_eq!(pairs, 6);
+}
+
+#[test]
+fn runner_api_test() {
+    let days: Vec<u32> = aoc25::runner::select_days("3..=5")
+        .unwrap()
+        .iter()
+        .map(|p| p.day)
+        .collect();
+    assert_eq!(days, [3, 4, 5]);
+
+    let f = lookup("6-1").unwrap();
+    let example = &aoc25::solution::find(6).unwrap().examples[0];
+    let answer = aoc25::runner::run_part("6-1", f, example.input, &Params::default()).unwrap();
+    assert_eq!(answer, example.answer);
+}